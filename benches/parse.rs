@@ -0,0 +1,175 @@
+use core::fmt::Write;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+/// A document with `lines` key/value pairs of each kind of value.
+fn document(lines: usize) -> String {
+    let mut doc = String::new();
+    for i in 0..lines {
+        writeln!(doc, "string-{i} = \"value {i}\"").unwrap();
+        writeln!(doc, "literal-{i} = 'value {i}'").unwrap();
+        writeln!(doc, "integer-{i} = {i}").unwrap();
+        writeln!(doc, "float-{i} = {i}.5e-3").unwrap();
+        writeln!(doc, "boolean-{i} = {}", i % 2 == 0).unwrap();
+        writeln!(doc, "datetime-{i} = 1979-05-27T07:32:00Z").unwrap();
+        writeln!(doc, "array-{i} = [1, 2.0, \"three\", false]").unwrap();
+        writeln!(doc, "table-{i} = {{ a = {i}, b = \"b\", c = [true] }}").unwrap();
+    }
+
+    doc
+}
+
+fn values(c: &mut Criterion) {
+    let doc = document(1000);
+    let mut group = c.benchmark_group("values");
+    group.throughput(Throughput::Bytes(doc.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| tomling::parse(black_box(&doc)).unwrap())
+    });
+    group.finish();
+}
+
+/// A document with `lines` arrays of datetimes and numbers looking alike.
+fn datetimes(lines: usize) -> String {
+    let mut doc = String::new();
+    for i in 0..lines {
+        writeln!(
+            doc,
+            "d-{i} = [1979-05-27T07:32:00Z, 1979-05-27, 07:32:00.999, 1979, 1979.5, 12]"
+        )
+        .unwrap();
+    }
+
+    doc
+}
+
+fn datetime_values(c: &mut Criterion) {
+    let doc = datetimes(1000);
+    let mut group = c.benchmark_group("datetimes");
+    group.throughput(Throughput::Bytes(doc.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| tomling::parse(black_box(&doc)).unwrap())
+    });
+    group.finish();
+}
+
+/// A document with `lines` arrays of numbers, with and without underscores.
+fn numbers(lines: usize) -> String {
+    let mut doc = String::new();
+    for i in 0..lines {
+        writeln!(
+            doc,
+            "n-{i} = [{i}, -{i}, 0x{i:x}, {i}.25, {i}e-2, 1_000_000, 0xdead_beef, 3.141_592]"
+        )
+        .unwrap();
+    }
+
+    doc
+}
+
+fn number_values(c: &mut Criterion) {
+    let doc = numbers(1000);
+    let mut group = c.benchmark_group("numbers");
+    group.throughput(Throughput::Bytes(doc.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| tomling::parse(black_box(&doc)).unwrap())
+    });
+    group.finish();
+}
+
+fn manifest(c: &mut Criterion) {
+    let doc = include_str!("../Cargo.toml");
+    let mut group = c.benchmark_group("manifest");
+    group.throughput(Throughput::Bytes(doc.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| tomling::parse(black_box(doc)).unwrap())
+    });
+    group.finish();
+}
+
+/// A generated document with a long array, with runs of comments between its elements.
+fn commented_array(entries: usize) -> String {
+    let mut doc = String::from("checksums = [\n");
+    for i in 0..entries {
+        for line in 0..8 {
+            writeln!(doc, "    # Entry {i}, line {line}.").unwrap();
+        }
+        writeln!(doc, "\n    {i}, # Pinned.\r\n").unwrap();
+    }
+    doc.push_str("]\n");
+
+    doc
+}
+
+fn comments(c: &mut Criterion) {
+    let doc = commented_array(10_000);
+    let mut group = c.benchmark_group("comments");
+    group.throughput(Throughput::Bytes(doc.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| tomling::parse(black_box(&doc)).unwrap())
+    });
+    group.finish();
+}
+
+/// A `Cargo.toml` with `deps` dependencies, in each of the dependency tables.
+fn huge_manifest(deps: usize) -> String {
+    let mut doc = String::from("[package]\nname = \"huge\"\nversion = \"0.1.0\"\n");
+    for table in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        writeln!(doc, "\n[{table}]").unwrap();
+        for i in 0..deps {
+            writeln!(
+                doc,
+                "dep-{i} = {{ version = \"1.{i}\", default-features = false, \
+                 features = [\"std\"], optional = true }}"
+            )
+            .unwrap();
+        }
+    }
+
+    doc
+}
+
+fn huge_manifest_keys(c: &mut Criterion) {
+    let doc = huge_manifest(2000);
+    let mut group = c.benchmark_group("huge-manifest");
+    group.throughput(Throughput::Bytes(doc.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| tomling::parse(black_box(&doc)).unwrap())
+    });
+    group.bench_function("parse-owned", |b| {
+        b.iter(|| tomling::parse(black_box(&doc)).unwrap().into_owned())
+    });
+    group.finish();
+}
+
+/// Look up every key of a table with 10k keys.
+///
+/// Run with and without the `hashbrown` feature to compare the map backing `Table`.
+fn table_get(c: &mut Criterion) {
+    let keys = (0..10_000).map(|i| format!("key-{i}")).collect::<Vec<_>>();
+    let table = keys
+        .iter()
+        .map(|k| (k.as_str().into(), tomling::Value::Integer(1)))
+        .collect::<tomling::Table<'_>>();
+    let mut group = c.benchmark_group("table");
+    group.throughput(Throughput::Elements(keys.len() as u64));
+    group.bench_function("get", |b| {
+        b.iter(|| {
+            for key in &keys {
+                black_box(table.get(black_box(key)).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    values,
+    datetime_values,
+    number_values,
+    manifest,
+    huge_manifest_keys,
+    comments,
+    table_get
+);
+criterion_main!(benches);
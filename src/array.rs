@@ -17,9 +17,18 @@ impl<'a> Array<'a> {
         Self(Vec::new())
     }
 
+    /// Create a new array with space for at least `capacity` values.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
     /// Push a value to the array.
-    pub fn push(&mut self, value: Value<'a>) {
+    ///
+    /// Returns the array so calls can be chained.
+    pub fn push(&mut self, value: Value<'a>) -> &mut Self {
         self.0.push(value);
+
+        self
     }
 
     /// Get the value at the given index.\
@@ -46,6 +55,36 @@ impl<'a> Array<'a> {
     pub fn iter(&self) -> Iter<'_, 'a> {
         Iter::new(self)
     }
+
+    /// A mutable iterator over the array.
+    pub fn iter_mut(&mut self) -> IterMut<'_, 'a> {
+        IterMut::new(self)
+    }
+
+    /// The strings in the array, if all the values are strings.
+    pub fn as_str_vec(&self) -> Option<Vec<&str>> {
+        self.iter().map(Value::as_str).collect()
+    }
+
+    /// The integers in the array, if all the values are integers.
+    pub fn as_i64_vec(&self) -> Option<Vec<i64>> {
+        self.iter().map(Value::as_i64).collect()
+    }
+
+    /// The floats in the array, if all the values are floats.
+    pub fn as_f64_vec(&self) -> Option<Vec<f64>> {
+        self.iter().map(Value::as_f64).collect()
+    }
+
+    /// The booleans in the array, if all the values are booleans.
+    pub fn as_bool_vec(&self) -> Option<Vec<bool>> {
+        self.iter().map(Value::as_bool).collect()
+    }
+
+    /// Convert into an array that owns all its data.
+    pub fn into_owned(self) -> Array<'static> {
+        Array(self.0.into_iter().map(Value::into_owned).collect())
+    }
 }
 
 impl<'a> Deref for Array<'a> {
@@ -62,6 +101,16 @@ impl DerefMut for Array<'_> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Array<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
 impl<'a> FromIterator<Value<'a>> for Array<'a> {
     fn from_iter<I>(iter: I) -> Self
     where
@@ -71,6 +120,15 @@ impl<'a> FromIterator<Value<'a>> for Array<'a> {
     }
 }
 
+impl<'a> Extend<Value<'a>> for Array<'a> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Value<'a>>,
+    {
+        self.0.extend(iter)
+    }
+}
+
 /// An iterator over the values of an array.
 #[derive(Debug)]
 pub struct Iter<'i, 'a> {
@@ -93,6 +151,28 @@ impl<'i, 'a> Iterator for Iter<'i, 'a> {
     }
 }
 
+/// A mutable iterator over the values of an array.
+#[derive(Debug)]
+pub struct IterMut<'i, 'a> {
+    iter: alloc::slice::IterMut<'i, Value<'a>>,
+}
+
+impl<'i, 'a> IterMut<'i, 'a> {
+    fn new(array: &'i mut Array<'a>) -> IterMut<'i, 'a> {
+        IterMut {
+            iter: array.0.iter_mut(),
+        }
+    }
+}
+
+impl<'i, 'a> Iterator for IterMut<'i, 'a> {
+    type Item = &'i mut Value<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
 impl<'a> IntoIterator for Array<'a> {
     type Item = Value<'a>;
     type IntoIter = IntoIter<'a>;
@@ -26,38 +26,51 @@ impl<'value> TryFrom<Value<'value>> for Author<'value> {
     type Error = crate::Error;
 
     fn try_from(value: Value<'value>) -> Result<Author<'value>, Self::Error> {
+        let error = || crate::Error::Convert {
+            from: "tomling::Value",
+            to: "tomling::cargo::Author",
+            path: None,
+        };
         match value {
             Value::String(Cow::Borrowed(s)) => {
-                use winnow::{
-                    combinator::{separated_pair, terminated},
-                    token::take_until,
-                    Parser,
-                };
-
-                let parse_author = |s| {
-                    separated_pair(
-                        take_until::<_, _, ()>(1.., " <"),
-                        " <",
-                        terminated(take_until(1.., '>'), '>'),
-                    )
-                    .map(|(name, email)| (name, Some(email)))
-                    .parse(s)
-                };
-
-                let (name, email) = parse_author(s).unwrap_or((s, None));
+                let (name, email) = split_author(s).ok_or_else(error)?;
                 Ok(Author {
                     name: name.into(),
                     email: email.map(Into::into),
                 })
             }
-            _ => Err(crate::Error::Convert {
-                from: "tomling::Value",
-                to: "tomling::cargo::Author",
-            }),
+            Value::String(Cow::Owned(s)) => {
+                let (name, email) = split_author(&s).ok_or_else(error)?;
+                Ok(Author {
+                    name: Cow::Owned(name.into()),
+                    email: email.map(|email| Cow::Owned(email.into())),
+                })
+            }
+            _ => Err(error()),
         }
     }
 }
 
+/// Split an author string into the name and the email address, if any.
+///
+/// The email address is the contents of the trailing `<...>`, and the name is everything before
+/// it, trimmed. Returns `None` if the email address is empty.
+fn split_author(s: &str) -> Option<(&str, Option<&str>)> {
+    let s = s.trim();
+    let Some(rest) = s.strip_suffix('>') else {
+        return Some((s, None));
+    };
+    let Some((name, email)) = rest.rsplit_once('<') else {
+        return Some((s, None));
+    };
+    let email = email.trim();
+    if email.is_empty() {
+        return None;
+    }
+
+    Some((name.trim_end(), Some(email)))
+}
+
 impl<'a, 'de: 'a> Deserialize<'de> for Author<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Author<'a>, D::Error>
     where
@@ -1,6 +1,8 @@
 use alloc::{borrow::Cow, vec::Vec};
 use serde::Deserialize;
 
+use super::impl_target_config;
+
 /// A bench target.
 #[derive(Debug, Deserialize)]
 pub struct Bench<'b> {
@@ -20,41 +22,6 @@ impl Bench<'_> {
     pub fn name(&self) -> &str {
         &self.name
     }
-
-    /// The path to the source of the bench.
-    pub fn path(&self) -> Option<&str> {
-        self.path.as_deref()
-    }
-
-    /// Whether or not the bench is tested by default by `cargo test`.
-    pub fn test(&self) -> Option<bool> {
-        self.test
-    }
-
-    /// Whether or not the bench is benchmarked by default by `cargo bench`.
-    pub fn bench(&self) -> Option<bool> {
-        self.bench
-    }
-
-    /// Whether or not the documentation is built by default by `cargo doc` for this bench.
-    pub fn doc(&self) -> Option<bool> {
-        self.doc
-    }
-
-    /// Indicates that the bench is a test harness.
-    pub fn harness(&self) -> Option<bool> {
-        self.harness
-    }
-
-    /// The Rust edition this bench requires.
-    pub fn edition(&self) -> Option<&str> {
-        self.edition.as_deref()
-    }
-
-    /// The required features of the bench.
-    pub fn required_features(&self) -> Option<impl Iterator<Item = &str>> {
-        self.required_features
-            .as_ref()
-            .map(|v| v.iter().map(|s| &**s))
-    }
 }
+
+impl_target_config!(Bench, "bench", required_features);
@@ -0,0 +1,218 @@
+use alloc::{boxed::Box, vec::Vec};
+use winnow::{
+    ascii::multispace0,
+    combinator::{cut_err, delimited, fail, opt, preceded, separated, terminated},
+    error::{ContextError, StrContext, StrContextValue},
+    token::{one_of, take_until, take_while},
+    ModalResult, Parser,
+};
+
+use crate::{Error, ParseError};
+
+/// A parsed `cfg(...)` expression, as used in the keys of [`super::Targets`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgExpr<'c> {
+    /// `all(...)`: true if all the expressions are true.
+    All(Vec<CfgExpr<'c>>),
+    /// `any(...)`: true if any of the expressions is true.
+    Any(Vec<CfgExpr<'c>>),
+    /// `not(...)`: true if the expression is false.
+    Not(Box<CfgExpr<'c>>),
+    /// A key-value option, e.g `target_os = "linux"`.
+    Eq(&'c str, &'c str),
+    /// A bare option, e.g `unix`.
+    Flag(&'c str),
+}
+
+impl<'c> CfgExpr<'c> {
+    /// Parse a `cfg(...)` expression.
+    pub fn parse(s: &'c str) -> Result<Self, Error> {
+        delimited(
+            (multispace0, "cfg", multispace0, '('),
+            predicate(0),
+            (multispace0, ')', multispace0),
+        )
+        .parse(s)
+        .map_err(|e| Error::Parse(ParseError::new(e.into_inner())))
+    }
+
+    /// Whether the expression is true on the platform described by `ctx`.
+    pub fn matches(&self, ctx: &CfgCtx<'_>) -> bool {
+        self.eval(|key, value| match value {
+            Some(value) => ctx.values.contains(&(key, value)),
+            None => ctx.flags.contains(&key),
+        })
+    }
+
+    /// Evaluate the expression, using `predicate` to decide whether each option is set.
+    ///
+    /// `predicate` gets the name of the option and for key-value options, the value.
+    ///
+    /// ```
+    /// use tomling::cargo::CfgExpr;
+    ///
+    /// let expr = CfgExpr::parse(r#"cfg(any(target_os = "macos", windows))"#).unwrap();
+    /// assert!(expr.eval(|key, value| key == "target_os" && value == Some("macos")));
+    /// assert!(!expr.eval(|key, _| key == "unix"));
+    /// ```
+    pub fn eval<F>(&self, mut predicate: F) -> bool
+    where
+        F: FnMut(&'c str, Option<&'c str>) -> bool,
+    {
+        self.eval_with(&mut predicate)
+    }
+
+    fn eval_with<F>(&self, predicate: &mut F) -> bool
+    where
+        F: FnMut(&'c str, Option<&'c str>) -> bool,
+    {
+        match self {
+            CfgExpr::All(exprs) => exprs.iter().all(|e| e.eval_with(predicate)),
+            CfgExpr::Any(exprs) => exprs.iter().any(|e| e.eval_with(predicate)),
+            CfgExpr::Not(expr) => !expr.eval_with(predicate),
+            CfgExpr::Eq(key, value) => predicate(key, Some(value)),
+            CfgExpr::Flag(name) => predicate(name, None),
+        }
+    }
+}
+
+/// The description of a platform, to evaluate [`CfgExpr`]s against.
+///
+/// ```
+/// use tomling::cargo::{CfgCtx, CfgExpr};
+///
+/// let ctx = CfgCtx::new()
+///     .flag("unix")
+///     .value("target_os", "linux")
+///     .value("target_family", "unix");
+/// let expr = CfgExpr::parse(r#"cfg(all(unix, not(target_os = "macos")))"#).unwrap();
+/// assert!(expr.matches(&ctx));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CfgCtx<'c> {
+    triple: Option<&'c str>,
+    flags: Vec<&'c str>,
+    values: Vec<(&'c str, &'c str)>,
+}
+
+impl<'c> CfgCtx<'c> {
+    /// Create an empty context, in which only negations are true.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the target triple, matched against the target keys that aren't `cfg(...)` expressions.
+    pub fn triple(mut self, triple: &'c str) -> Self {
+        self.triple = Some(triple);
+
+        self
+    }
+
+    /// Set a bare option, e.g `unix`.
+    pub fn flag(mut self, name: &'c str) -> Self {
+        self.flags.push(name);
+
+        self
+    }
+
+    /// Set a key-value option, e.g `target_os = "linux"`.
+    ///
+    /// Keys can have multiple values, e.g `target_feature`.
+    pub fn value(mut self, key: &'c str, value: &'c str) -> Self {
+        self.values.push((key, value));
+
+        self
+    }
+
+    /// Whether a target key (either a `cfg(...)` expression or a target triple) matches.
+    ///
+    /// Invalid `cfg(...)` expressions never match.
+    pub(crate) fn matches_key(&self, key: &str) -> bool {
+        if key.trim_start().starts_with("cfg") {
+            CfgExpr::parse(key).map_or(false, |expr| expr.matches(self))
+        } else {
+            self.triple == Some(key)
+        }
+    }
+}
+
+/// Parses a predicate, nested in `depth` `all`, `any` or `not` expressions.
+fn predicate<'c>(
+    depth: usize,
+) -> impl FnMut(&mut &'c str) -> ModalResult<CfgExpr<'c>, ContextError> {
+    move |input| {
+        if depth >= MAX_NESTING {
+            return cut_err(fail)
+                .context(StrContext::Label("cfg expression"))
+                .context(StrContext::Expected(StrContextValue::Description(
+                    "less deeply nested expressions",
+                )))
+                .parse_next(input);
+        }
+
+        let name = preceded(multispace0, identifier).parse_next(input)?;
+        if opt((multispace0, '(')).parse_next(input)?.is_some() {
+            let expr = match name {
+                "all" => list(depth).map(CfgExpr::All).parse_next(input)?,
+                "any" => list(depth).map(CfgExpr::Any).parse_next(input)?,
+                "not" => terminated(predicate(depth + 1), multispace0)
+                    .map(|e| CfgExpr::Not(Box::new(e)))
+                    .parse_next(input)?,
+                _ => {
+                    return cut_err(fail)
+                        .context(StrContext::Label("cfg expression"))
+                        .context(StrContext::Expected(StrContextValue::Description(
+                            "`all`, `any` or `not`",
+                        )))
+                        .parse_next(input)
+                }
+            };
+            cut_err(')').parse_next(input)?;
+
+            return Ok(expr);
+        }
+
+        let value =
+            opt(preceded((multispace0, '=', multispace0), cut_err(string))).parse_next(input)?;
+        multispace0.parse_next(input)?;
+
+        Ok(match value {
+            Some(value) => CfgExpr::Eq(name, value),
+            None => CfgExpr::Flag(name),
+        })
+    }
+}
+
+/// Parses the comma-separated arguments of `all` or `any`, nested in `depth` expressions.
+fn list<'c>(
+    depth: usize,
+) -> impl FnMut(&mut &'c str) -> ModalResult<Vec<CfgExpr<'c>>, ContextError> {
+    move |input| {
+        terminated(
+            separated(0.., predicate(depth + 1), (multispace0, ',')),
+            (opt((multispace0, ',')), multispace0),
+        )
+        .parse_next(input)
+    }
+}
+
+/// Parses an identifier, e.g `target_os`.
+fn identifier<'c>(input: &mut &'c str) -> ModalResult<&'c str, ContextError> {
+    (
+        one_of(|c: char| c.is_ascii_alphabetic() || c == '_'),
+        take_while(0.., |c: char| c.is_ascii_alphanumeric() || c == '_'),
+    )
+        .take()
+        .context(StrContext::Label("cfg identifier"))
+        .parse_next(input)
+}
+
+/// Parses a double-quoted string, without escapes.
+fn string<'c>(input: &mut &'c str) -> ModalResult<&'c str, ContextError> {
+    delimited('"', take_until(0.., '"'), '"')
+        .context(StrContext::Label("string"))
+        .parse_next(input)
+}
+
+/// The maximum nesting depth of `all`, `any` and `not` expressions.
+const MAX_NESTING: usize = 32;
@@ -1,6 +1,10 @@
 //! The dependencies of a package.
 
-use alloc::{borrow::Cow, collections::BTreeMap, vec::Vec};
+use alloc::{
+    borrow::Cow,
+    collections::{btree_map, BTreeMap},
+    vec::Vec,
+};
 use serde::{de, Deserialize};
 
 use crate::{Table, Value};
@@ -15,18 +19,64 @@ impl<'d> Dependencies<'d> {
         self.0.get(name)
     }
 
+    /// The number of dependencies.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no dependencies.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether there is a dependency named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
     /// Iterate over the dependencies.
-    pub fn iter(&self) -> impl Iterator<Item = (&str, &Dependency<'d>)> {
-        self.0.iter().map(|(k, v)| (&**k, v))
+    pub fn iter(&self) -> DependenciesIter<'_, 'd> {
+        DependenciesIter {
+            iter: self.0.iter(),
+        }
+    }
+}
+
+impl<'s, 'd> IntoIterator for &'s Dependencies<'d> {
+    type Item = (&'s str, &'s Dependency<'d>);
+    type IntoIter = DependenciesIter<'s, 'd>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the dependencies.
+#[derive(Debug)]
+pub struct DependenciesIter<'s, 'd> {
+    iter: btree_map::Iter<'s, Cow<'d, str>, Dependency<'d>>,
+}
+
+impl<'s, 'd> Iterator for DependenciesIter<'s, 'd> {
+    type Item = (&'s str, &'s Dependency<'d>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, v)| (&**k, v))
     }
 }
 
 /// A dependency.
+///
+/// Both the simple (`regex = "1.5"`) and the detailed (`regex = { version = "1.5" }`) forms of a
+/// dependency are represented by this type, so `regex = "1.5"` is equal to
+/// `regex = { version = "1.5" }`. Since a detailed dependency doesn't need to specify a version
+/// (e.g path or git dependencies), [`Dependency::version`] returns an `Option`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Dependency<'d> {
     version: Option<Cow<'d, str>>,
     optional: Option<bool>,
     features: Option<Vec<Cow<'d, str>>>,
+    default_features: Option<bool>,
     workspace: Option<bool>,
     package: Option<Cow<'d, str>>,
     source: Option<Source<'d>>,
@@ -50,6 +100,11 @@ impl Dependency<'_> {
         self.features.as_ref().map(|v| v.iter().map(|s| &**s))
     }
 
+    /// Whether the default features of the dependency are enabled.
+    pub fn default_features(&self) -> Option<bool> {
+        self.default_features
+    }
+
     /// Inherit from the workspace.
     pub fn workspace(&self) -> Option<bool> {
         self.workspace
@@ -77,6 +132,7 @@ impl<'d, 'de: 'd> Deserialize<'de> for Dependency<'d> {
                 version: Some(version),
                 optional: None,
                 features: None,
+                default_features: None,
                 workspace: None,
                 package: None,
                 source: None,
@@ -98,6 +154,7 @@ impl<'d, 'de: 'd> Deserialize<'de> for Dependency<'d> {
                         )),
                     })
                     .transpose()?;
+                let default_features = table.get("default-features").and_then(|v| v.as_bool());
                 let workspace = table.get("workspace").map(|v| v.as_bool().unwrap_or(false));
                 let package = get_string(&table, "package")?;
                 let source = Source::new(&table)?;
@@ -106,6 +163,7 @@ impl<'d, 'de: 'd> Deserialize<'de> for Dependency<'d> {
                     version,
                     optional,
                     features,
+                    default_features,
                     workspace,
                     package,
                     source,
@@ -0,0 +1,72 @@
+use alloc::vec::Vec;
+
+use crate::{Table, Value};
+
+/// The docs.rs settings of a package, from its `[package.metadata.docs.rs]` table.
+///
+/// Settings of an unexpected type are treated as absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocsRsMetadata<'m> {
+    all_features: Option<bool>,
+    features: Option<Vec<&'m str>>,
+    targets: Option<Vec<&'m str>>,
+    rustc_args: Option<Vec<&'m str>>,
+    rustdoc_args: Option<Vec<&'m str>>,
+    default_target: Option<&'m str>,
+}
+
+impl<'m> DocsRsMetadata<'m> {
+    /// Read the settings from the `docs.rs` table of the package metadata.
+    pub(crate) fn new(metadata: &'m Table<'_>) -> Option<Self> {
+        let table = metadata
+            .get("docs")
+            .and_then(Value::as_table)?
+            .get("rs")
+            .and_then(Value::as_table)?;
+        let strings = |key| {
+            table
+                .get(key)
+                .and_then(Value::as_array)
+                .map(|array| array.iter().filter_map(Value::as_str).collect())
+        };
+
+        Some(DocsRsMetadata {
+            all_features: table.get("all-features").and_then(Value::as_bool),
+            features: strings("features"),
+            targets: strings("targets"),
+            rustc_args: strings("rustc-args"),
+            rustdoc_args: strings("rustdoc-args"),
+            default_target: table.get("default-target").and_then(Value::as_str),
+        })
+    }
+
+    /// Whether to build the documentation with all features enabled.
+    pub fn all_features(&self) -> Option<bool> {
+        self.all_features
+    }
+
+    /// The features to enable when building the documentation.
+    pub fn features(&self) -> Option<impl Iterator<Item = &str>> {
+        self.features.as_ref().map(|v| v.iter().copied())
+    }
+
+    /// The targets to build the documentation for.
+    pub fn targets(&self) -> Option<impl Iterator<Item = &str>> {
+        self.targets.as_ref().map(|v| v.iter().copied())
+    }
+
+    /// The additional arguments to pass to `rustc`.
+    pub fn rustc_args(&self) -> Option<impl Iterator<Item = &str>> {
+        self.rustc_args.as_ref().map(|v| v.iter().copied())
+    }
+
+    /// The additional arguments to pass to `rustdoc`.
+    pub fn rustdoc_args(&self) -> Option<impl Iterator<Item = &str>> {
+        self.rustdoc_args.as_ref().map(|v| v.iter().copied())
+    }
+
+    /// The target to build the documentation for by default, e.g `x86_64-unknown-linux-gnu`.
+    pub fn default_target(&self) -> Option<&str> {
+        self.default_target
+    }
+}
@@ -1,7 +1,7 @@
 use alloc::{borrow::Cow, vec::Vec};
 use serde::Deserialize;
 
-use super::LibraryType;
+use super::{impl_target_config, LibraryType};
 
 /// An example target.
 #[derive(Debug, Deserialize)]
@@ -25,45 +25,10 @@ impl Example<'_> {
         &self.name
     }
 
-    /// The path to the source of the example.
-    pub fn path(&self) -> Option<&str> {
-        self.path.as_deref()
-    }
-
-    /// Whether or not the example is tested by default by `cargo test`.
-    pub fn test(&self) -> Option<bool> {
-        self.test
-    }
-
-    /// Whether or not the example is benchmarked by default by `cargo bench`.
-    pub fn bench(&self) -> Option<bool> {
-        self.bench
-    }
-
-    /// Whether or not the documentation is built by default by `cargo doc` for this example.
-    pub fn doc(&self) -> Option<bool> {
-        self.doc
-    }
-
-    /// Indicates that the example is a test harness.
-    pub fn harness(&self) -> Option<bool> {
-        self.harness
-    }
-
-    /// The Rust edition this example requires.
-    pub fn edition(&self) -> Option<&str> {
-        self.edition.as_deref()
-    }
-
-    /// The required features of the example.
-    pub fn required_features(&self) -> Option<impl Iterator<Item = &str>> {
-        self.required_features
-            .as_ref()
-            .map(|v| v.iter().map(|s| &**s))
-    }
-
     /// The library type of the example.
     pub fn library_type(&self) -> Option<&[LibraryType]> {
         self.library_type.as_deref()
     }
 }
+
+impl_target_config!(Example, "example", required_features);
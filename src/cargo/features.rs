@@ -1,18 +1,176 @@
-use alloc::{borrow::Cow, collections::BTreeMap, vec::Vec};
+use alloc::{
+    borrow::Cow,
+    collections::{btree_map, BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
 use serde::Deserialize;
 
 /// A Cargo features section.
 #[derive(Debug, Deserialize)]
-pub struct Features<'f>(#[serde(borrow)] BTreeMap<Cow<'f, str>, Vec<&'f str>>);
+pub struct Features<'f>(#[serde(borrow)] BTreeMap<Cow<'f, str>, Vec<Cow<'f, str>>>);
 
-impl Features<'_> {
+impl<'f> Features<'f> {
     /// Get the features by name.
-    pub fn by_name(&self, name: &str) -> Option<&[&str]> {
+    pub fn by_name(&self, name: &str) -> Option<&[Cow<'f, str>]> {
         self.0.get(name).map(|v| v.as_slice())
     }
 
+    /// Get the parsed references of the feature by name.
+    pub fn refs_by_name(&self, name: &str) -> Option<impl Iterator<Item = FeatureRef<'_>>> {
+        self.0
+            .get(name)
+            .map(|v| v.iter().map(|r| FeatureRef::parse(r)))
+    }
+
+    /// The number of features.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no features.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether there is a feature named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
     /// Iterate over the features.
-    pub fn iter(&self) -> impl Iterator<Item = (&str, &[&str])> {
-        self.0.iter().map(|(k, v)| (&**k, v.as_slice()))
+    pub fn iter(&self) -> FeaturesIter<'_, 'f> {
+        FeaturesIter {
+            iter: self.0.iter(),
+        }
+    }
+
+    /// The features transitively enabled by the feature named `name`, sorted by name.
+    ///
+    /// Only [`FeatureRef::Feature`] references are followed. References to features not in this
+    /// section (e.g the implicit features of optional dependencies) are included but not followed
+    /// any further.
+    /// The feature itself is only included if it's part of a cycle.
+    pub fn closure(&self, name: &str) -> Vec<&str> {
+        let mut enabled = BTreeSet::new();
+        let mut pending = vec![name];
+        while let Some(feature) = pending.pop() {
+            let Some(refs) = self.0.get(feature) else {
+                continue;
+            };
+            for r in refs {
+                if let FeatureRef::Feature(r) = FeatureRef::parse(r) {
+                    if enabled.insert(r) {
+                        pending.push(r);
+                    }
+                }
+            }
+        }
+
+        enabled.into_iter().collect()
+    }
+}
+
+impl<'s, 'f> IntoIterator for &'s Features<'f> {
+    type Item = (&'s str, &'s [Cow<'f, str>]);
+    type IntoIter = FeaturesIter<'s, 'f>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the features.
+#[derive(Debug)]
+pub struct FeaturesIter<'s, 'f> {
+    iter: btree_map::Iter<'s, Cow<'f, str>, Vec<Cow<'f, str>>>,
+}
+
+impl<'s, 'f> Iterator for FeaturesIter<'s, 'f> {
+    type Item = (&'s str, &'s [Cow<'f, str>]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, v)| (&**k, v.as_slice()))
+    }
+}
+
+/// A reference in the list of a feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureRef<'r> {
+    /// Another feature of the crate, e.g `"blocking-api"`.
+    Feature(&'r str),
+    /// An optional dependency, e.g `"dep:rand"`.
+    OptionalDep(&'r str),
+    /// A feature of a dependency, e.g `"zvariant/uuid"` or `"serde?/derive"`.
+    DepFeature {
+        /// The name of the dependency.
+        dep: &'r str,
+        /// The name of the feature.
+        feature: &'r str,
+        /// Whether the dependency is only enabled if something else enables it (`?/`).
+        weak: bool,
+    },
+}
+
+impl<'r> FeatureRef<'r> {
+    /// Parse a feature reference.
+    pub fn parse(s: &'r str) -> Self {
+        if let Some(dep) = s.strip_prefix("dep:") {
+            return FeatureRef::OptionalDep(dep);
+        }
+
+        match s.split_once('/') {
+            Some((dep, feature)) => match dep.strip_suffix('?') {
+                Some(dep) => FeatureRef::DepFeature {
+                    dep,
+                    feature,
+                    weak: true,
+                },
+                None => FeatureRef::DepFeature {
+                    dep,
+                    feature,
+                    weak: false,
+                },
+            },
+            None => FeatureRef::Feature(s),
+        }
+    }
+}
+
+/// A feature required by a target, that the package doesn't have.
+///
+/// Returned by [`Manifest::validate_required_features`](super::Manifest::validate_required_features).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownFeature<'m> {
+    pub(crate) target_kind: &'static str,
+    pub(crate) target: &'m str,
+    pub(crate) feature: &'m str,
+}
+
+impl<'m> UnknownFeature<'m> {
+    /// The kind of the target, as in the manifest: `"bin"`, `"example"`, `"test"` or `"bench"`.
+    pub fn target_kind(&self) -> &'static str {
+        self.target_kind
+    }
+
+    /// The name of the target.
+    pub fn target(&self) -> &'m str {
+        self.target
+    }
+
+    /// The name of the unknown feature.
+    pub fn feature(&self) -> &'m str {
+        self.feature
+    }
+}
+
+impl fmt::Display for UnknownFeature<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} `{}` requires the unknown feature `{}`",
+            self.target_kind, self.target, self.feature
+        )
     }
 }
@@ -1,6 +1,8 @@
 use alloc::{borrow::Cow, vec::Vec};
 use serde::Deserialize;
 
+use super::impl_target_config;
+
 /// A library target.
 #[derive(Debug, Deserialize)]
 pub struct Library<'l> {
@@ -24,26 +26,6 @@ impl Library<'_> {
         self.name.as_deref()
     }
 
-    /// The path to the source of the library.
-    pub fn path(&self) -> Option<&str> {
-        self.path.as_deref()
-    }
-
-    /// Whether or not the library is tested by default by `cargo test`.
-    pub fn test(&self) -> Option<bool> {
-        self.test
-    }
-
-    /// Whether or not the library is benchmarked by default by `cargo bench`.
-    pub fn bench(&self) -> Option<bool> {
-        self.bench
-    }
-
-    /// Whether or not the documentation is built by default by `cargo doc` for this library.
-    pub fn doc(&self) -> Option<bool> {
-        self.doc
-    }
-
     /// Whether or not the documentation is tested by default by `cargo test`.
     pub fn doctest(&self) -> Option<bool> {
         self.doctest
@@ -54,22 +36,14 @@ impl Library<'_> {
         self.proc_macro
     }
 
-    /// Indicates that the library is a test harness.
-    pub fn harness(&self) -> Option<bool> {
-        self.harness
-    }
-
-    /// The Rust edition this library requires.
-    pub fn edition(&self) -> Option<&str> {
-        self.edition.as_deref()
-    }
-
     /// The crate type of the library.
     pub fn library_type(&self) -> Option<&[LibraryType]> {
         self.library_type.as_deref()
     }
 }
 
+impl_target_config!(Library, "library");
+
 /// The crate type.
 #[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[non_exhaustive]
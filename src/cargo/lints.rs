@@ -0,0 +1,167 @@
+use alloc::{borrow::Cow, collections::BTreeMap};
+use serde::{de, Deserialize};
+
+use crate::{Table, Value};
+
+/// The lints, i.e the `[lints]` or `[workspace.lints]` section, grouped by tool.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Lints<'l>(#[serde(borrow)] BTreeMap<Cow<'l, str>, LintGroup<'l>>);
+
+impl<'l> Lints<'l> {
+    /// Get the lints of a tool by name, e.g `"clippy"`.
+    pub fn tool(&self, name: &str) -> Option<&LintGroup<'l>> {
+        self.0.get(name)
+    }
+
+    /// The lints of the Rust compiler.
+    pub fn rust(&self) -> Option<&LintGroup<'l>> {
+        self.tool("rust")
+    }
+
+    /// The lints of Clippy.
+    pub fn clippy(&self) -> Option<&LintGroup<'l>> {
+        self.tool("clippy")
+    }
+
+    /// The lints of rustdoc.
+    pub fn rustdoc(&self) -> Option<&LintGroup<'l>> {
+        self.tool("rustdoc")
+    }
+
+    /// Iterate over the tools and their lints.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &LintGroup<'l>)> {
+        self.0.iter().map(|(k, v)| (&**k, v))
+    }
+}
+
+impl<'l> TryFrom<Value<'l>> for Lints<'l> {
+    type Error = crate::Error;
+
+    fn try_from(value: Value<'l>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Table(table) => crate::serde::from_table(table),
+            _ => Err(crate::Error::Convert {
+                from: "tomling::Value",
+                to: "tomling::cargo::Lints",
+                path: None,
+            }),
+        }
+    }
+}
+
+/// The lints of a tool, e.g `[lints.clippy]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LintGroup<'l>(#[serde(borrow)] BTreeMap<Cow<'l, str>, Lint<'l>>);
+
+impl<'l> LintGroup<'l> {
+    /// Get a lint by name, e.g `"unsafe_code"`.
+    pub fn by_name(&self, name: &str) -> Option<&Lint<'l>> {
+        self.0.get(name)
+    }
+
+    /// The number of lints.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no lints.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the lints.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Lint<'l>)> {
+        self.0.iter().map(|(k, v)| (&**k, v))
+    }
+}
+
+/// The configuration of a lint.
+///
+/// Both the simple (`unsafe_code = "forbid"`) and the detailed
+/// (`unsafe_code = { level = "forbid", priority = 1 }`) forms are represented by this type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint<'l> {
+    level: LintLevel,
+    priority: Option<i64>,
+    options: Table<'l>,
+}
+
+impl<'l> Lint<'l> {
+    /// The level of the lint.
+    pub fn level(&self) -> LintLevel {
+        self.level
+    }
+
+    /// The priority of the lint, relative to the other lints of the tool.
+    pub fn priority(&self) -> Option<i64> {
+        self.priority
+    }
+
+    /// Get a lint-specific option by name, e.g `"check-cfg"` for `unexpected_cfgs`.
+    pub fn option(&self, name: &str) -> Option<&Value<'l>> {
+        self.options.get(name)
+    }
+
+    /// The lint-specific options.
+    pub fn options(&self) -> &Table<'l> {
+        &self.options
+    }
+}
+
+impl<'l, 'de: 'l> Deserialize<'de> for Lint<'l> {
+    fn deserialize<D>(deserializer: D) -> Result<Lint<'l>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (level, mut options) = match Value::deserialize(deserializer)? {
+            Value::String(level) => (level, Table::new()),
+            Value::Table(mut table) => match table.remove("level") {
+                Some(Value::String(level)) => (level, table),
+                Some(_) => {
+                    return Err(de::Error::invalid_type(
+                        de::Unexpected::Other("not a string"),
+                        &"a lint level",
+                    ))
+                }
+                None => return Err(de::Error::missing_field("level")),
+            },
+            _ => {
+                return Err(de::Error::invalid_type(
+                    de::Unexpected::Other("not a string or table"),
+                    &"a string or table",
+                ))
+            }
+        };
+        let level = LintLevel::deserialize(de::value::StrDeserializer::<D::Error>::new(&level))?;
+        let priority = match options.remove("priority") {
+            Some(Value::Integer(priority)) => Some(priority),
+            Some(_) => {
+                return Err(de::Error::invalid_type(
+                    de::Unexpected::Other("not an integer"),
+                    &"an integer priority",
+                ))
+            }
+            None => None,
+        };
+
+        Ok(Lint {
+            level,
+            priority,
+            options,
+        })
+    }
+}
+
+/// The level of a [`Lint`].
+#[derive(Debug, Deserialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Don't report the lint.
+    Allow,
+    /// Report the lint as a warning.
+    Warn,
+    /// Report the lint as an error.
+    Deny,
+    /// Report the lint as an error, without allowing it to be overridden.
+    Forbid,
+}
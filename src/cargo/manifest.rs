@@ -1,9 +1,18 @@
-use alloc::vec::Vec;
+use alloc::{collections::BTreeSet, vec::Vec};
+use core::ops::Deref;
 use serde::Deserialize;
 
-use super::{Bench, Binary, Dependencies, Features, Library, Package, Targets, Test, Workspace};
+use crate::Table;
+
+use super::{
+    package::WorkspaceInheritable, Bench, Binary, Dependencies, Example, FeatureRef, Features,
+    Library, Lints, Package, Profiles, TargetRef, Targets, Test, UnknownFeature, Workspace,
+};
 
 /// A parsed `Cargo.toml` file.
+///
+/// To also keep the parsed table, for keys not (yet) covered by the typed API, use
+/// [`ManifestWithRaw`] instead.
 #[derive(Debug, Deserialize)]
 pub struct Manifest<'c> {
     #[serde(borrow)]
@@ -22,14 +31,40 @@ pub struct Manifest<'c> {
     #[serde(rename = "bin")]
     binaries: Option<Vec<Binary<'c>>>,
     #[serde(rename = "example")]
-    examples: Option<Vec<Binary<'c>>>,
+    examples: Option<Vec<Example<'c>>>,
     #[serde(rename = "test")]
     tests: Option<Vec<Test<'c>>>,
     #[serde(rename = "bench")]
     benches: Option<Vec<Bench<'c>>>,
+    badges: Option<Table<'c>>,
+    lints: Option<WorkspaceInheritable<Lints<'c>>>,
+    #[serde(rename = "profile")]
+    profiles: Option<Profiles<'c>>,
 }
 
 impl<'c> Manifest<'c> {
+    /// Read and parse the `Cargo.toml` file at `path`.
+    ///
+    /// Unlike [`crate::from_str`], the returned manifest owns all its data so it isn't tied to
+    /// the lifetime of a buffer. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn from_path<P>(path: P) -> Result<Manifest<'static>, crate::Error>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| crate::Error::Io(crate::IoError::new(&e, path)))?;
+        let table = crate::parse(&contents)?.into_owned();
+
+        Manifest::from_table(table)
+    }
+
+    /// Deserialize a manifest from a parsed table.
+    pub fn from_table(table: Table<'c>) -> Result<Self, crate::Error> {
+        crate::serde::from_table(table)
+    }
+
     /// The package name.
     pub fn package(&self) -> Option<&Package<'c>> {
         self.package.as_ref()
@@ -40,6 +75,23 @@ impl<'c> Manifest<'c> {
         self.workspace.as_ref()
     }
 
+    /// Whether the manifest defines a package, a workspace or both.
+    ///
+    /// Returns `None` if it defines neither, which Cargo rejects.
+    pub fn package_or_workspace(&self) -> Option<ManifestKind> {
+        match (&self.package, &self.workspace) {
+            (Some(_), None) => Some(ManifestKind::Package),
+            (None, Some(_)) => Some(ManifestKind::Workspace),
+            (Some(_), Some(_)) => Some(ManifestKind::Both),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether the manifest is a virtual workspace, i.e a workspace without a package.
+    pub fn is_virtual_workspace(&self) -> bool {
+        self.package_or_workspace() == Some(ManifestKind::Workspace)
+    }
+
     /// The dependencies.
     pub fn dependencies(&self) -> Option<&Dependencies<'c>> {
         self.dependencies.as_ref()
@@ -76,10 +128,30 @@ impl<'c> Manifest<'c> {
     }
 
     /// The examples.
-    pub fn examples(&self) -> Option<&[Binary<'c>]> {
+    pub fn examples(&self) -> Option<&[Example<'c>]> {
         self.examples.as_deref()
     }
 
+    /// All the build targets: the library, the binaries, the examples, the tests and the benches,
+    /// in that order.
+    ///
+    /// Only the targets declared in the manifest are included, not the ones Cargo discovers
+    /// automatically.
+    pub fn all_targets(&self) -> impl Iterator<Item = TargetRef<'_, 'c>> {
+        let library = self.library().map(TargetRef::Library);
+        let binaries = self.binaries().unwrap_or_default().iter();
+        let examples = self.examples().unwrap_or_default().iter();
+        let tests = self.tests().unwrap_or_default().iter();
+        let benches = self.benches().unwrap_or_default().iter();
+
+        library
+            .into_iter()
+            .chain(binaries.map(TargetRef::Binary))
+            .chain(examples.map(TargetRef::Example))
+            .chain(tests.map(TargetRef::Test))
+            .chain(benches.map(TargetRef::Bench))
+    }
+
     /// The tests.
     pub fn tests(&self) -> Option<&[Test<'c>]> {
         self.tests.as_deref()
@@ -89,4 +161,161 @@ impl<'c> Manifest<'c> {
     pub fn benches(&self) -> Option<&[Bench<'c>]> {
         self.benches.as_deref()
     }
+
+    /// The badges, e.g `maintenance = { status = "actively-developed" }`.
+    pub fn badges(&self) -> Option<&Table<'c>> {
+        self.badges.as_ref()
+    }
+
+    /// The profiles.
+    pub fn profiles(&self) -> Option<&Profiles<'c>> {
+        self.profiles.as_ref()
+    }
+
+    /// The lints of the package, possibly inherited from the workspace (`workspace = true`).
+    pub fn lints(&self) -> Option<&WorkspaceInheritable<Lints<'c>>> {
+        self.lints.as_ref()
+    }
+
+    /// Check that the `required-features` of the targets are all features of the package.
+    ///
+    /// The features of the package are the ones in the `[features]` section, along with the
+    /// implicit features of the optional dependencies not referred to with `dep:`. Features of
+    /// dependencies (e.g `"serde/derive"`) are not checked.
+    pub fn validate_required_features(&self) -> Result<(), Vec<UnknownFeature<'_>>> {
+        let features = self.features();
+        let dep_refs: BTreeSet<_> = features
+            .into_iter()
+            .flat_map(|features| features.iter())
+            .flat_map(|(_, refs)| refs.iter().map(|r| FeatureRef::parse(r)))
+            .filter_map(|r| match r {
+                FeatureRef::OptionalDep(dep) => Some(dep),
+                _ => None,
+            })
+            .collect();
+        let target_deps = self
+            .targets()
+            .into_iter()
+            .flat_map(|targets| targets.iter())
+            .flat_map(|(_, target)| [target.dependencies(), target.build_dependencies()]);
+        let implicit: BTreeSet<_> = [self.dependencies(), self.build_dependencies()]
+            .into_iter()
+            .chain(target_deps)
+            .flatten()
+            .flat_map(|deps| deps.iter())
+            .filter(|(name, dep)| dep.optional() == Some(true) && !dep_refs.contains(name))
+            .map(|(name, _)| name)
+            .collect();
+        let is_known = |feature| {
+            features.map_or(false, |features| features.contains(feature))
+                || implicit.contains(feature)
+        };
+
+        let mut unknown = Vec::new();
+        for target in self.all_targets() {
+            for feature in target.required_features().into_iter().flatten() {
+                match FeatureRef::parse(feature) {
+                    FeatureRef::Feature(feature) if !is_known(feature) => {
+                        unknown.push(UnknownFeature {
+                            target_kind: target.kind(),
+                            target: target.name().unwrap_or_default(),
+                            feature,
+                        })
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(unknown)
+        }
+    }
+}
+
+/// A [`Manifest`] along with the parsed table it was deserialized from.
+///
+/// Useful for keys not (yet) covered by the typed API. Dereferences to the [`Manifest`].
+///
+/// ```
+/// use tomling::{cargo::ManifestWithRaw, Value};
+///
+/// let manifest: ManifestWithRaw = tomling::from_str(
+///     r#"
+///     [package]
+///     name = "foo"
+///     version = "0.1.0"
+///
+///     [some-future-section]
+///     enabled = true
+///     "#,
+/// )
+/// .unwrap();
+/// assert_eq!(manifest.package().unwrap().name(), "foo");
+/// let section = manifest.raw().get("some-future-section").and_then(Value::as_table);
+/// assert_eq!(section.and_then(|s| s.get("enabled")), Some(&Value::Boolean(true)));
+/// ```
+#[derive(Debug)]
+pub struct ManifestWithRaw<'c> {
+    manifest: Manifest<'c>,
+    raw: Table<'c>,
+}
+
+impl<'c> ManifestWithRaw<'c> {
+    /// Deserialize a manifest from a parsed table, keeping the table.
+    pub fn from_table(table: Table<'c>) -> Result<Self, crate::Error> {
+        let manifest = Manifest::from_table(table.clone())?;
+
+        Ok(Self {
+            manifest,
+            raw: table,
+        })
+    }
+
+    /// The manifest.
+    pub fn manifest(&self) -> &Manifest<'c> {
+        &self.manifest
+    }
+
+    /// The parsed table the manifest was deserialized from.
+    pub fn raw(&self) -> &Table<'c> {
+        &self.raw
+    }
+
+    /// Split into the manifest and the parsed table.
+    pub fn into_parts(self) -> (Manifest<'c>, Table<'c>) {
+        (self.manifest, self.raw)
+    }
+}
+
+impl<'c> Deref for ManifestWithRaw<'c> {
+    type Target = Manifest<'c>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manifest
+    }
+}
+
+impl<'de: 'c, 'c> Deserialize<'de> for ManifestWithRaw<'c> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let table = Table::deserialize(deserializer)?;
+
+        ManifestWithRaw::from_table(table).map_err(serde::de::Error::custom)
+    }
+}
+
+/// What a [`Manifest`] defines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestKind {
+    /// A package, that may be a member of a workspace defined elsewhere.
+    Package,
+    /// A virtual workspace, i.e a workspace without a root package.
+    Workspace,
+    /// A workspace with a root package.
+    Both,
 }
@@ -5,29 +5,72 @@
 mod author;
 mod bench;
 mod binary;
+mod cfg;
 pub mod dependency;
+mod docs_rs;
 mod example;
 mod features;
 mod library;
+mod lints;
 mod manifest;
 pub mod package;
+mod profile;
 mod resolver_version;
 mod rust_edition;
+mod rust_version;
 mod target;
+mod target_config;
 mod test;
 pub mod workspace;
 
 pub use author::*;
 pub use bench::*;
 pub use binary::*;
-pub use dependency::{Dependencies, Dependency};
+pub use cfg::*;
+pub use dependency::{Dependencies, DependenciesIter, Dependency};
+pub use docs_rs::*;
 pub use example::*;
 pub use features::*;
 pub use library::*;
+pub use lints::*;
 pub use manifest::*;
-pub use package::Package;
+pub use package::{Package, Publish};
+pub use profile::*;
 pub use resolver_version::*;
 pub use rust_edition::*;
+pub use rust_version::*;
 pub use target::*;
+pub use target_config::*;
 pub use test::*;
 pub use workspace::Workspace;
+
+/// Parse only the `[package]` section of a manifest.
+///
+/// Useful when only a few fields of the package are needed, e.g its name and version. The whole
+/// document still has to be parsed, since the keys of the package could be anywhere in it, but only
+/// the package is deserialized. So the rest of the manifest is not validated, beyond being valid
+/// TOML.
+///
+/// ```
+/// let package = tomling::cargo::package_only(
+///     r#"
+///     [package]
+///     name = "example"
+///     version = "0.1.0"
+///
+///     [dependencies]
+///     serde = { version = 1.0 }
+///     "#,
+/// )
+/// .unwrap();
+/// assert_eq!(package.name(), "example");
+/// assert_eq!(package.version().unwrap().uninherited(), Some("0.1.0"));
+/// ```
+pub fn package_only(input: &str) -> Result<Package<'_>, crate::Error> {
+    let mut table = crate::parse(input)?;
+
+    match table.remove("package") {
+        Some(package) => Package::try_from(package).map_err(|e| e.with_key("package")),
+        None => Err(serde::de::Error::missing_field("package")),
+    }
+}
@@ -5,7 +5,7 @@ use core::borrow::Borrow;
 use alloc::{borrow::Cow, vec::Vec};
 use serde::Deserialize;
 
-use super::{Author, ResolverVersion, RustEdition};
+use super::{workspace, Author, DocsRsMetadata, ResolverVersion, RustEdition};
 use crate::{Table, Value};
 
 /// The package information.
@@ -24,13 +24,14 @@ pub struct Package<'p> {
     homepage: Option<WorkspaceInheritable<Cow<'p, str>>>,
     repository: Option<WorkspaceInheritable<Cow<'p, str>>>,
     license: Option<WorkspaceInheritable<Cow<'p, str>>>,
+    #[serde(rename = "license-file")]
     license_file: Option<WorkspaceInheritable<Cow<'p, str>>>,
     keywords: Option<WorkspaceInheritable<Vec<Cow<'p, str>>>>,
     categories: Option<WorkspaceInheritable<Vec<Cow<'p, str>>>>,
     workspace: Option<Cow<'p, str>>,
     build: Option<Cow<'p, str>>,
     links: Option<Cow<'p, str>>,
-    publish: Option<WorkspaceInheritable<bool>>,
+    publish: Option<WorkspaceInheritable<Publish<'p>>>,
     metadata: Option<Table<'p>>,
     include: Option<WorkspaceInheritable<Vec<Cow<'p, str>>>>,
     exclude: Option<WorkspaceInheritable<Vec<Cow<'p, str>>>>,
@@ -60,6 +61,8 @@ impl<'p> Package<'p> {
     }
 
     /// The required Rust version.
+    ///
+    /// Parse it into a [`RustVersion`](super::RustVersion) to compare it numerically.
     pub fn rust_version(&self) -> Option<WorkspaceInheritable<&str>> {
         self.rust_version.as_ref().map(WorkspaceInheritable::borrow)
     }
@@ -137,9 +140,9 @@ impl<'p> Package<'p> {
         self.links.as_deref()
     }
 
-    /// Whether the package should be published.
-    pub fn publish(&self) -> Option<WorkspaceInheritable<bool>> {
-        self.publish.clone()
+    /// Whether and where the package should be published.
+    pub fn publish(&self) -> Option<&WorkspaceInheritable<Publish<'p>>> {
+        self.publish.as_ref()
     }
 
     /// The package metadata.
@@ -147,6 +150,28 @@ impl<'p> Package<'p> {
         self.metadata.as_ref()
     }
 
+    /// Get a value from the package metadata, by its dot-separated path.
+    ///
+    /// For example, `metadata_path("docs.rs.all-features")` returns the `all-features` value of
+    /// the `[package.metadata.docs.rs]` table. Keys containing dots can not be looked up.
+    pub fn metadata_path(&self, path: &str) -> Option<&Value<'p>> {
+        let mut keys = path.split('.');
+        let mut value = self.metadata.as_ref()?.get(keys.next()?)?;
+        for key in keys {
+            value = match value {
+                Value::Table(table) => table.get(key)?,
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    /// The docs.rs settings, from the `docs.rs` table of the package metadata.
+    pub fn docs_rs_metadata(&self) -> Option<DocsRsMetadata<'_>> {
+        self.metadata.as_ref().and_then(DocsRsMetadata::new)
+    }
+
     /// The paths to include.
     pub fn include(&self) -> Option<WorkspaceInheritable<impl Iterator<Item = &str>>> {
         self.include
@@ -190,6 +215,101 @@ impl<'p> Package<'p> {
     pub fn resolver(&self) -> Option<ResolverVersion> {
         self.resolver
     }
+
+    /// Resolve the properties inherited from the workspace.
+    ///
+    /// Returns a copy of this package, where the properties inherited from the workspace (e.g
+    /// `version.workspace = true`) are replaced by their value in `workspace`, the
+    /// `[workspace.package]` table of the workspace manifest. Properties not set in `workspace`
+    /// are left inherited.
+    pub fn resolve_inherited(&self, workspace: &workspace::Package<'p>) -> Package<'p> {
+        fn resolve<W: Clone>(property: &mut Option<WorkspaceInheritable<W>>, value: &Option<W>) {
+            if let (Some(WorkspaceInheritable::Inherited), Some(value)) = (&*property, value) {
+                *property = Some(WorkspaceInheritable::Uninherited(value.clone()));
+            }
+        }
+
+        let mut package = self.clone();
+        resolve(&mut package.version, &workspace.version);
+        resolve(&mut package.edition, &workspace.edition);
+        resolve(&mut package.rust_version, &workspace.rust_version);
+        resolve(&mut package.authors, &workspace.authors);
+        resolve(&mut package.description, &workspace.description);
+        resolve(&mut package.documentation, &workspace.documentation);
+        resolve(&mut package.readme, &workspace.readme);
+        resolve(&mut package.homepage, &workspace.homepage);
+        resolve(&mut package.repository, &workspace.repository);
+        resolve(&mut package.license, &workspace.license);
+        resolve(&mut package.license_file, &workspace.license_file);
+        resolve(&mut package.keywords, &workspace.keywords);
+        resolve(&mut package.categories, &workspace.categories);
+        resolve(&mut package.publish, &workspace.publish);
+        resolve(&mut package.include, &workspace.include);
+        resolve(&mut package.exclude, &workspace.exclude);
+
+        package
+    }
+}
+
+impl<'p> TryFrom<Value<'p>> for Package<'p> {
+    type Error = crate::Error;
+
+    fn try_from(value: Value<'p>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Table(table) => crate::serde::from_table(table),
+            _ => Err(crate::Error::Convert {
+                from: "tomling::Value",
+                to: "tomling::cargo::Package",
+                path: None,
+            }),
+        }
+    }
+}
+
+/// Whether and where a package can be published, i.e the `publish` field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Publish<'p> {
+    /// Whether the package can be published to any registry, e.g `publish = false`.
+    Flag(bool),
+    /// The only registries the package can be published to, e.g `publish = ["my-registry"]`.
+    Registries(Vec<Cow<'p, str>>),
+}
+
+impl Publish<'_> {
+    /// Whether the package can be published at all, i.e unless `publish = false` or `publish = []`.
+    pub fn is_publishable(&self) -> bool {
+        match self {
+            Self::Flag(publish) => *publish,
+            Self::Registries(registries) => !registries.is_empty(),
+        }
+    }
+}
+
+impl<'p> TryFrom<Value<'p>> for Publish<'p> {
+    type Error = crate::Error;
+
+    fn try_from(value: Value<'p>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Boolean(publish) => Ok(Self::Flag(publish)),
+            Value::Array(_) => value.try_into().map(Self::Registries),
+            _ => Err(crate::Error::Convert {
+                from: "tomling::Value",
+                to: "tomling::cargo::Publish",
+                path: None,
+            }),
+        }
+    }
+}
+
+impl<'p, 'de: 'p> Deserialize<'de> for Publish<'p> {
+    fn deserialize<D>(deserializer: D) -> Result<Publish<'p>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Value::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
 }
 
 /// The property inheritable from the workspace.
@@ -264,7 +384,7 @@ where
         D: serde::Deserializer<'de>,
     {
         match <Value<'value>>::deserialize(deserializer)? {
-            Value::Table(table) => {
+            Value::Table(table) if table.get("workspace").is_some() => {
                 table
                     .get("workspace")
                     .and_then(|v| (v == &Value::Boolean(true)).then_some(()))
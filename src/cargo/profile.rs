@@ -0,0 +1,231 @@
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, vec, vec::Vec};
+use serde::{de, Deserialize};
+
+use crate::Value;
+
+/// The profiles, i.e the `[profile]` section.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Profiles<'p>(#[serde(borrow)] BTreeMap<Cow<'p, str>, Profile<'p>>);
+
+impl<'p> Profiles<'p> {
+    /// Get a profile by name, as specified in the manifest.
+    pub fn by_name(&self, name: &str) -> Option<&Profile<'p>> {
+        self.0.get(name)
+    }
+
+    /// Iterate over the profiles.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Profile<'p>)> {
+        self.0.iter().map(|(k, v)| (&**k, v))
+    }
+
+    /// Resolve the profile named `name`, applying inheritance.
+    ///
+    /// The settings of a profile override the ones of the profile it inherits from, as specified
+    /// by its `inherits` key. The built-in `test` and `bench` profiles inherit from `dev` and
+    /// `release` respectively. Only the settings in the manifest are taken into account, not
+    /// Cargo's defaults.
+    ///
+    /// Returns `None` if neither the profile nor any of the profiles it inherits from are in the
+    /// manifest. Inheritance cycles are broken at the first repeated profile.
+    pub fn resolve(&self, name: &str) -> Option<Profile<'p>> {
+        let mut names = vec![name];
+        let mut chain = Vec::new();
+        let mut name = name;
+        loop {
+            let profile = self.0.get(name);
+            if let Some(profile) = profile {
+                chain.push(profile);
+            }
+            let inherits = match (profile.and_then(Profile::inherits), name) {
+                (Some(inherits), _) => inherits,
+                (None, "test") => "dev",
+                (None, "bench") => "release",
+                (None, _) => break,
+            };
+            if names.contains(&inherits) {
+                break;
+            }
+            names.push(inherits);
+            name = inherits;
+        }
+
+        let (first, rest) = chain.split_last()?;
+        let mut resolved = (*first).clone();
+        for profile in rest.iter().rev() {
+            resolved.overlay(profile);
+        }
+
+        Some(resolved)
+    }
+}
+
+/// A profile, e.g `[profile.release]`.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Profile<'p> {
+    #[serde(borrow)]
+    inherits: Option<Cow<'p, str>>,
+    opt_level: Option<OptLevel>,
+    debug: Option<Value<'p>>,
+    split_debuginfo: Option<Cow<'p, str>>,
+    strip: Option<Value<'p>>,
+    debug_assertions: Option<bool>,
+    overflow_checks: Option<bool>,
+    lto: Option<Value<'p>>,
+    panic: Option<Cow<'p, str>>,
+    incremental: Option<bool>,
+    codegen_units: Option<u32>,
+    rpath: Option<bool>,
+    build_override: Option<Box<Profile<'p>>>,
+    package: Option<BTreeMap<Cow<'p, str>, Profile<'p>>>,
+}
+
+impl<'p> Profile<'p> {
+    /// The profile this profile inherits from.
+    pub fn inherits(&self) -> Option<&str> {
+        self.inherits.as_deref()
+    }
+
+    /// The optimization level.
+    pub fn opt_level(&self) -> Option<OptLevel> {
+        self.opt_level
+    }
+
+    /// The amount of debug information, either a boolean, an integer or a string.
+    pub fn debug(&self) -> Option<&Value<'p>> {
+        self.debug.as_ref()
+    }
+
+    /// How debug information is split, e.g `"packed"`.
+    pub fn split_debuginfo(&self) -> Option<&str> {
+        self.split_debuginfo.as_deref()
+    }
+
+    /// What to strip from the binary, either a boolean or a string.
+    pub fn strip(&self) -> Option<&Value<'p>> {
+        self.strip.as_ref()
+    }
+
+    /// Whether debug assertions are enabled.
+    pub fn debug_assertions(&self) -> Option<bool> {
+        self.debug_assertions
+    }
+
+    /// Whether overflow checks are enabled.
+    pub fn overflow_checks(&self) -> Option<bool> {
+        self.overflow_checks
+    }
+
+    /// The link-time optimization, either a boolean or a string.
+    pub fn lto(&self) -> Option<&Value<'p>> {
+        self.lto.as_ref()
+    }
+
+    /// The panic strategy, e.g `"abort"`.
+    pub fn panic(&self) -> Option<&str> {
+        self.panic.as_deref()
+    }
+
+    /// Whether incremental compilation is enabled.
+    pub fn incremental(&self) -> Option<bool> {
+        self.incremental
+    }
+
+    /// The number of code generation units.
+    pub fn codegen_units(&self) -> Option<u32> {
+        self.codegen_units
+    }
+
+    /// Whether rpath is enabled.
+    pub fn rpath(&self) -> Option<bool> {
+        self.rpath
+    }
+
+    /// The overrides for build scripts, proc macros and their dependencies.
+    pub fn build_override(&self) -> Option<&Profile<'p>> {
+        self.build_override.as_deref()
+    }
+
+    /// The overrides for a specific package, e.g `[profile.dev.package.foo]`.
+    ///
+    /// `"*"` is the override for all the dependencies.
+    pub fn package(&self, name: &str) -> Option<&Profile<'p>> {
+        self.package.as_ref()?.get(name)
+    }
+
+    /// Iterate over the package overrides.
+    pub fn packages(&self) -> Option<impl Iterator<Item = (&str, &Profile<'p>)>> {
+        self.package
+            .as_ref()
+            .map(|packages| packages.iter().map(|(k, v)| (&**k, v)))
+    }
+
+    /// Override the settings of this profile with the ones set in `other`.
+    fn overlay(&mut self, other: &Profile<'p>) {
+        fn set<T: Clone>(this: &mut Option<T>, other: &Option<T>) {
+            if other.is_some() {
+                this.clone_from(other);
+            }
+        }
+
+        set(&mut self.inherits, &other.inherits);
+        set(&mut self.opt_level, &other.opt_level);
+        set(&mut self.debug, &other.debug);
+        set(&mut self.split_debuginfo, &other.split_debuginfo);
+        set(&mut self.strip, &other.strip);
+        set(&mut self.debug_assertions, &other.debug_assertions);
+        set(&mut self.overflow_checks, &other.overflow_checks);
+        set(&mut self.lto, &other.lto);
+        set(&mut self.panic, &other.panic);
+        set(&mut self.incremental, &other.incremental);
+        set(&mut self.codegen_units, &other.codegen_units);
+        set(&mut self.rpath, &other.rpath);
+        if let Some(other) = &other.build_override {
+            self.build_override
+                .get_or_insert_with(Default::default)
+                .overlay(other);
+        }
+        if let Some(other) = &other.package {
+            let packages = self.package.get_or_insert_with(Default::default);
+            for (name, other) in other {
+                packages.entry(name.clone()).or_default().overlay(other);
+            }
+        }
+    }
+}
+
+/// The optimization level of a [`Profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptLevel {
+    /// A numeric level, from 0 (no optimizations) to 3 (all optimizations).
+    Level(u8),
+    /// Optimize for binary size (`"s"`).
+    Size,
+    /// Optimize for binary size, also turning off loop vectorization (`"z"`).
+    MinSize,
+}
+
+impl<'de> Deserialize<'de> for OptLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::Integer(level @ 0..=3) => Ok(OptLevel::Level(level as u8)),
+            Value::String(s) => match &*s {
+                // Cargo also accepts the numeric levels as strings.
+                "0" | "1" | "2" | "3" => Ok(OptLevel::Level(s.as_bytes()[0] - b'0')),
+                "s" => Ok(OptLevel::Size),
+                "z" => Ok(OptLevel::MinSize),
+                _ => Err(de::Error::invalid_value(
+                    de::Unexpected::Str(&s),
+                    &"0, 1, 2, 3, \"s\" or \"z\"",
+                )),
+            },
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::Other("not an optimization level"),
+                &"0, 1, 2, 3, \"s\" or \"z\"",
+            )),
+        }
+    }
+}
@@ -1,13 +1,42 @@
+use crate::Value;
 use serde::Deserialize;
 
 /// The resolver version.
-#[derive(Debug, Deserialize, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum ResolverVersion {
     /// Resolver version 1.
-    #[serde(rename = "1")]
     V1,
     /// Resolver version 2.
-    #[serde(rename = "2")]
     V2,
 }
+
+/// Both the string (`resolver = "2"`) and the integer (`resolver = 2`) forms are accepted.
+impl TryFrom<Value<'_>> for ResolverVersion {
+    type Error = crate::Error;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(value) if value == "1" => Ok(Self::V1),
+            Value::String(value) if value == "2" => Ok(Self::V2),
+            Value::Integer(1) => Ok(Self::V1),
+            Value::Integer(2) => Ok(Self::V2),
+            _ => Err(crate::Error::Convert {
+                from: "tomling::Value",
+                to: "tomling::cargo::ResolverVersion",
+                path: None,
+            }),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ResolverVersion {
+    fn deserialize<D>(deserializer: D) -> Result<ResolverVersion, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        value.try_into().map_err(serde::de::Error::custom)
+    }
+}
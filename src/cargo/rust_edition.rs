@@ -2,42 +2,72 @@ use crate::Value;
 use serde::Deserialize;
 
 /// The Rust edition.
-#[derive(Debug, Deserialize, Copy, Clone, PartialEq, Eq)]
+///
+/// Editions are ordered chronologically, and the default is 2015, as is the case for Cargo when a
+/// package doesn't specify its edition.
+///
+/// ```
+/// use tomling::cargo::RustEdition;
+///
+/// assert!(RustEdition::E2021 >= RustEdition::E2018);
+/// assert_eq!(RustEdition::default().year(), 2015);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub enum RustEdition {
     /// Edition 2015.
-    #[serde(rename = "2015")]
+    #[default]
     E2015,
     /// Edition 2018.
-    #[serde(rename = "2018")]
     E2018,
     /// Edition 2021.
-    #[serde(rename = "2021")]
     E2021,
     /// Edition 2024.
-    #[serde(rename = "2024")]
     E2024,
 }
 
+impl RustEdition {
+    /// The year of the edition, e.g `2021`.
+    pub fn year(&self) -> u16 {
+        match self {
+            Self::E2015 => 2015,
+            Self::E2018 => 2018,
+            Self::E2021 => 2021,
+            Self::E2024 => 2024,
+        }
+    }
+}
+
+/// Both the string (`edition = "2021"`) and the integer (`edition = 2021`) forms are accepted.
 impl TryFrom<Value<'_>> for RustEdition {
     type Error = crate::Error;
 
     fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
         match value {
-            Value::String(value) => match value.as_ref() {
-                "2015" => Ok(Self::E2015),
-                "2018" => Ok(Self::E2018),
-                "2021" => Ok(Self::E2021),
-                "2024" => Ok(Self::E2024),
-                _ => Err(crate::Error::Convert {
-                    from: "tomling::Value",
-                    to: "tomling::cargo::RustEdition",
-                }),
-            },
+            Value::String(value) if value == "2015" => Ok(Self::E2015),
+            Value::String(value) if value == "2018" => Ok(Self::E2018),
+            Value::String(value) if value == "2021" => Ok(Self::E2021),
+            Value::String(value) if value == "2024" => Ok(Self::E2024),
+            Value::Integer(2015) => Ok(Self::E2015),
+            Value::Integer(2018) => Ok(Self::E2018),
+            Value::Integer(2021) => Ok(Self::E2021),
+            Value::Integer(2024) => Ok(Self::E2024),
             _ => Err(crate::Error::Convert {
                 from: "tomling::Value",
                 to: "tomling::cargo::RustEdition",
+                path: None,
             }),
         }
     }
 }
+
+impl<'de> Deserialize<'de> for RustEdition {
+    fn deserialize<D>(deserializer: D) -> Result<RustEdition, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        value.try_into().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,79 @@
+use core::{fmt, str::FromStr};
+
+/// A Rust version, e.g the `rust-version` of a package.
+///
+/// The minor and patch components are optional and default to zero, so `1.80` is equal to
+/// `1.80.0`. The ordering is numerical, so `1.9` is lower than `1.80`.
+///
+/// ```
+/// use tomling::cargo::RustVersion;
+///
+/// let msrv: RustVersion = "1.70".parse().unwrap();
+/// assert!(msrv < RustVersion::new(1, 80, 0));
+/// assert_eq!(msrv.to_string(), "1.70.0");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RustVersion {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl RustVersion {
+    /// Create a new version.
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// The major version.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// The minor version.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// The patch version.
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+}
+
+impl FromStr for RustVersion {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || crate::Error::Convert {
+            from: "&str",
+            to: "tomling::cargo::RustVersion",
+            path: None,
+        };
+        let mut components = [0; 3];
+        let mut parts = s.split('.');
+        for (i, part) in parts.by_ref().take(3).enumerate() {
+            // `u32::from_str` also accepts a leading `+`.
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(error());
+            }
+            components[i] = part.parse().map_err(|_| error())?;
+        }
+        if parts.next().is_some() {
+            return Err(error());
+        }
+        let [major, minor, patch] = components;
+
+        Ok(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for RustVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
@@ -1,7 +1,11 @@
-use alloc::{borrow::Cow, collections::BTreeMap};
+use alloc::{
+    borrow::Cow,
+    collections::{btree_map, BTreeMap},
+};
 use serde::Deserialize;
 
-use super::Dependencies;
+use super::{CfgCtx, CfgExpr, Dependencies};
+use crate::Error;
 
 /// The set of target-specific options.
 #[derive(Debug, Deserialize)]
@@ -13,9 +17,69 @@ impl<'t> Targets<'t> {
         self.0.get(name)
     }
 
+    /// The number of targets.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no targets.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether there is a target named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
     /// Iterate over the targets.
-    pub fn iter(&self) -> impl Iterator<Item = (&str, &Target<'t>)> {
-        self.0.iter().map(|(k, v)| (&**k, v))
+    pub fn iter(&self) -> TargetsIter<'_, 't> {
+        TargetsIter {
+            iter: self.0.iter(),
+        }
+    }
+
+    /// Iterate over the targets specified by a `cfg(...)` expression, with the parsed expression.
+    ///
+    /// The targets specified by a target triple are skipped.
+    pub fn iter_cfg(&self) -> impl Iterator<Item = (Result<CfgExpr<'_>, Error>, &Target<'t>)> {
+        self.iter()
+            .filter(|(key, _)| key.trim_start().starts_with("cfg"))
+            .map(|(key, target)| (CfgExpr::parse(key), target))
+    }
+
+    /// Iterate over the targets that apply to the platform described by `ctx`.
+    ///
+    /// Targets specified by a `cfg(...)` expression match if the expression is true for `ctx`,
+    /// and the ones specified by a target triple match if it's the triple of `ctx`.
+    pub fn for_cfg<'s>(
+        &'s self,
+        ctx: &'s CfgCtx<'_>,
+    ) -> impl Iterator<Item = (&'s str, &'s Target<'t>)> {
+        self.iter().filter(|(key, _)| ctx.matches_key(key))
+    }
+}
+
+impl<'s, 't> IntoIterator for &'s Targets<'t> {
+    type Item = (&'s str, &'s Target<'t>);
+    type IntoIter = TargetsIter<'s, 't>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the targets.
+#[derive(Debug)]
+pub struct TargetsIter<'s, 't> {
+    iter: btree_map::Iter<'s, Cow<'t, str>, Target<'t>>,
+}
+
+impl<'s, 't> Iterator for TargetsIter<'s, 't> {
+    type Item = (&'s str, &'s Target<'t>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, v)| (&**k, v))
     }
 }
 
@@ -0,0 +1,242 @@
+use alloc::borrow::Cow;
+
+use super::{Bench, Binary, Example, Library, Test};
+
+/// The configuration common to all build targets.
+///
+/// Implemented by [`Library`](super::Library), [`Binary`](super::Binary),
+/// [`Example`](super::Example), [`Test`](super::Test) and [`Bench`](super::Bench), so that generic
+/// code can handle any kind of target. The name isn't part of it, since only the one of the library
+/// is optional, but [`TargetRef::name`] gives it for any kind of target.
+///
+/// ```
+/// use tomling::cargo::{Manifest, TargetConfig};
+///
+/// let manifest: Manifest = tomling::from_str(
+///     r#"
+///     [package]
+///     name = "example"
+///     version = "0.1.0"
+///
+///     [[bin]]
+///     name = "cli"
+///     required-features = ["cli"]
+///
+///     [[example]]
+///     name = "demo"
+///     path = "demo.rs"
+///     "#,
+/// )
+/// .unwrap();
+///
+/// fn required_features<T: TargetConfig>(targets: &[T]) -> Vec<&str> {
+///     targets.iter().filter_map(|t| t.required_features()).flatten().collect()
+/// }
+/// assert_eq!(required_features(manifest.binaries().unwrap()), ["cli"]);
+/// assert!(required_features(manifest.examples().unwrap()).is_empty());
+/// assert_eq!(manifest.examples().unwrap()[0].path(), Some("demo.rs"));
+/// ```
+pub trait TargetConfig {
+    /// The path to the source of the target.
+    fn path(&self) -> Option<&str>;
+
+    /// Whether or not the target is tested by default by `cargo test`.
+    fn test(&self) -> Option<bool>;
+
+    /// Whether or not the target is benchmarked by default by `cargo bench`.
+    fn bench(&self) -> Option<bool>;
+
+    /// Whether or not the documentation is built by default by `cargo doc` for this target.
+    fn doc(&self) -> Option<bool>;
+
+    /// Indicates that the target is a test harness.
+    fn harness(&self) -> Option<bool>;
+
+    /// The Rust edition this target requires.
+    fn edition(&self) -> Option<&str>;
+
+    /// The features required to build the target.
+    ///
+    /// Always `None` for the library, since Cargo doesn't allow it there.
+    fn required_features(&self) -> Option<RequiredFeatures<'_>>;
+}
+
+/// An iterator over the required features of a target.
+#[derive(Debug, Clone)]
+pub struct RequiredFeatures<'t>(core::slice::Iter<'t, Cow<'t, str>>);
+
+impl<'t> RequiredFeatures<'t> {
+    pub(crate) fn new(features: &'t [Cow<'t, str>]) -> Self {
+        Self(features.iter())
+    }
+}
+
+impl<'t> Iterator for RequiredFeatures<'t> {
+    type Item = &'t str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|f| &**f)
+    }
+}
+
+/// Implement the accessors of the configuration common to all targets, and [`TargetConfig`].
+///
+/// `$kind` is the kind of the target in the docs. With `required_features`, the target also gets
+/// a `required_features` accessor.
+macro_rules! impl_target_config {
+    ($target:ident, $kind:literal $(, $required_features:ident)?) => {
+        impl $target<'_> {
+            #[doc = concat!("The path to the source of the ", $kind, ".")]
+            pub fn path(&self) -> Option<&str> {
+                self.path.as_deref()
+            }
+
+            #[doc = concat!("Whether or not the ", $kind, " is tested by default by `cargo test`.")]
+            pub fn test(&self) -> Option<bool> {
+                self.test
+            }
+
+            #[doc = concat!(
+                "Whether or not the ", $kind, " is benchmarked by default by `cargo bench`."
+            )]
+            pub fn bench(&self) -> Option<bool> {
+                self.bench
+            }
+
+            #[doc = concat!(
+                "Whether or not the documentation is built by default by `cargo doc` for this ",
+                $kind,
+                "."
+            )]
+            pub fn doc(&self) -> Option<bool> {
+                self.doc
+            }
+
+            #[doc = concat!("Indicates that the ", $kind, " is a test harness.")]
+            pub fn harness(&self) -> Option<bool> {
+                self.harness
+            }
+
+            #[doc = concat!("The Rust edition this ", $kind, " requires.")]
+            pub fn edition(&self) -> Option<&str> {
+                self.edition.as_deref()
+            }
+
+            $(
+                #[doc = concat!("The required features of the ", $kind, ".")]
+                pub fn $required_features(&self) -> Option<$crate::cargo::RequiredFeatures<'_>> {
+                    self.required_features
+                        .as_deref()
+                        .map($crate::cargo::RequiredFeatures::new)
+                }
+            )?
+        }
+
+        impl $crate::cargo::TargetConfig for $target<'_> {
+            fn path(&self) -> Option<&str> {
+                $target::path(self)
+            }
+
+            fn test(&self) -> Option<bool> {
+                $target::test(self)
+            }
+
+            fn bench(&self) -> Option<bool> {
+                $target::bench(self)
+            }
+
+            fn doc(&self) -> Option<bool> {
+                $target::doc(self)
+            }
+
+            fn harness(&self) -> Option<bool> {
+                $target::harness(self)
+            }
+
+            fn edition(&self) -> Option<&str> {
+                $target::edition(self)
+            }
+
+            fn required_features(&self) -> Option<$crate::cargo::RequiredFeatures<'_>> {
+                impl_target_config!(@required_features self $(, $required_features)?)
+            }
+        }
+    };
+    (@required_features $self:ident, $required_features:ident) => {
+        $self.$required_features()
+    };
+    (@required_features $self:ident) => {
+        None
+    };
+}
+pub(crate) use impl_target_config;
+
+/// A reference to a build target of any kind.
+///
+/// Returned by [`Manifest::all_targets`](super::Manifest::all_targets).
+#[derive(Debug, Clone, Copy)]
+pub enum TargetRef<'s, 'c> {
+    /// The library.
+    Library(&'s Library<'c>),
+    /// A binary.
+    Binary(&'s Binary<'c>),
+    /// An example.
+    Example(&'s Example<'c>),
+    /// A test.
+    Test(&'s Test<'c>),
+    /// A benchmark.
+    Bench(&'s Bench<'c>),
+}
+
+impl<'s> TargetRef<'s, '_> {
+    /// The kind of the target, as in the manifest: `"lib"`, `"bin"`, `"example"`, `"test"` or
+    /// `"bench"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TargetRef::Library(_) => "lib",
+            TargetRef::Binary(_) => "bin",
+            TargetRef::Example(_) => "example",
+            TargetRef::Test(_) => "test",
+            TargetRef::Bench(_) => "bench",
+        }
+    }
+
+    /// The name of the target.
+    ///
+    /// Only optional for the library, which defaults to the name of the package.
+    pub fn name(&self) -> Option<&'s str> {
+        match *self {
+            TargetRef::Library(t) => t.name(),
+            TargetRef::Binary(t) => Some(t.name()),
+            TargetRef::Example(t) => Some(t.name()),
+            TargetRef::Test(t) => Some(t.name()),
+            TargetRef::Bench(t) => Some(t.name()),
+        }
+    }
+
+    /// The path to the source of the target.
+    pub fn path(&self) -> Option<&'s str> {
+        self.as_config().path()
+    }
+
+    /// The Rust edition this target requires.
+    pub fn edition(&self) -> Option<&'s str> {
+        self.as_config().edition()
+    }
+
+    /// The features required to build the target.
+    pub fn required_features(&self) -> Option<RequiredFeatures<'s>> {
+        self.as_config().required_features()
+    }
+
+    /// The target, as a [`TargetConfig`], for the rest of its configuration.
+    pub fn as_config(&self) -> &'s dyn TargetConfig {
+        match *self {
+            TargetRef::Library(t) => t,
+            TargetRef::Binary(t) => t,
+            TargetRef::Example(t) => t,
+            TargetRef::Test(t) => t,
+            TargetRef::Bench(t) => t,
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use alloc::{borrow::Cow, vec::Vec};
 use serde::Deserialize;
 
-use super::{Author, Dependencies, ResolverVersion, RustEdition};
+use super::{package::Publish, Author, Dependencies, Lints, ResolverVersion, RustEdition};
 use crate::Table;
 
 /// The package information.
@@ -18,7 +18,7 @@ pub struct Workspace<'p> {
     default_members: Option<Vec<Cow<'p, str>>>,
     exclude: Option<Vec<Cow<'p, str>>>,
     metadata: Option<Table<'p>>,
-    lints: Option<Table<'p>>,
+    lints: Option<Lints<'p>>,
 }
 
 impl<'p> Workspace<'p> {
@@ -60,7 +60,7 @@ impl<'p> Workspace<'p> {
     }
 
     /// The workspace lints.
-    pub fn lints(&self) -> Option<&Table<'p>> {
+    pub fn lints(&self) -> Option<&Lints<'p>> {
         self.lints.as_ref()
     }
 }
@@ -69,23 +69,24 @@ impl<'p> Workspace<'p> {
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Package<'p> {
     #[serde(borrow)]
-    version: Option<Cow<'p, str>>,
-    edition: Option<RustEdition>,
+    pub(super) version: Option<Cow<'p, str>>,
+    pub(super) edition: Option<RustEdition>,
     #[serde(rename = "rust-version")]
-    rust_version: Option<Cow<'p, str>>,
-    authors: Option<Vec<Author<'p>>>,
-    description: Option<Cow<'p, str>>,
-    documentation: Option<Cow<'p, str>>,
-    readme: Option<Cow<'p, str>>,
-    homepage: Option<Cow<'p, str>>,
-    repository: Option<Cow<'p, str>>,
-    license: Option<Cow<'p, str>>,
-    license_file: Option<Cow<'p, str>>,
-    keywords: Option<Vec<Cow<'p, str>>>,
-    categories: Option<Vec<Cow<'p, str>>>,
-    publish: Option<bool>,
-    include: Option<Vec<Cow<'p, str>>>,
-    exclude: Option<Vec<Cow<'p, str>>>,
+    pub(super) rust_version: Option<Cow<'p, str>>,
+    pub(super) authors: Option<Vec<Author<'p>>>,
+    pub(super) description: Option<Cow<'p, str>>,
+    pub(super) documentation: Option<Cow<'p, str>>,
+    pub(super) readme: Option<Cow<'p, str>>,
+    pub(super) homepage: Option<Cow<'p, str>>,
+    pub(super) repository: Option<Cow<'p, str>>,
+    pub(super) license: Option<Cow<'p, str>>,
+    #[serde(rename = "license-file")]
+    pub(super) license_file: Option<Cow<'p, str>>,
+    pub(super) keywords: Option<Vec<Cow<'p, str>>>,
+    pub(super) categories: Option<Vec<Cow<'p, str>>>,
+    pub(super) publish: Option<Publish<'p>>,
+    pub(super) include: Option<Vec<Cow<'p, str>>>,
+    pub(super) exclude: Option<Vec<Cow<'p, str>>>,
 }
 
 impl<'p> Package<'p> {
@@ -100,6 +101,8 @@ impl<'p> Package<'p> {
     }
 
     /// The required Rust version.
+    ///
+    /// Parse it into a [`RustVersion`](super::RustVersion) to compare it numerically.
     pub fn rust_version(&self) -> Option<&str> {
         self.rust_version.as_deref()
     }
@@ -154,9 +157,9 @@ impl<'p> Package<'p> {
         self.categories.as_ref().map(|v| v.iter().map(|s| &**s))
     }
 
-    /// Whether the package should be published.
-    pub fn publish(&self) -> Option<bool> {
-        self.publish
+    /// Whether and where the package should be published.
+    pub fn publish(&self) -> Option<&Publish<'p>> {
+        self.publish.as_ref()
     }
 
     /// The paths to include.
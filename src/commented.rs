@@ -0,0 +1,96 @@
+//! A TOML table, along with the comments attached to its values.
+
+use alloc::{borrow::Cow, collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{Table, Value};
+
+/// A parsed TOML document, along with the comments attached to its values and tables.
+///
+/// Created by [`crate::parse_with_comments`]. Comments are looked up by the dotted path of the
+/// value or table header they're attached to, e.g `package.name` or `dependencies."foo.bar"`, with
+/// the keys quoted as needed, as in a TOML document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentedTable<'i> {
+    table: Table<'i>,
+    comments: BTreeMap<String, ValueComments<'i>>,
+}
+
+impl<'i> CommentedTable<'i> {
+    pub(crate) fn new(table: Table<'i>) -> Self {
+        Self {
+            table,
+            comments: BTreeMap::new(),
+        }
+    }
+
+    /// The parsed table.
+    pub fn table(&self) -> &Table<'i> {
+        &self.table
+    }
+
+    /// Convert into the parsed table, dropping the comments.
+    pub fn into_table(self) -> Table<'i> {
+        self.table
+    }
+
+    /// The comments attached to the value or table header at the dotted `path`.
+    pub fn comments(&self, path: &str) -> Option<&ValueComments<'i>> {
+        self.comments.get(path)
+    }
+
+    /// Iterate over the dotted paths that have comments attached, along with the comments.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ValueComments<'i>)> {
+        self.comments.iter().map(|(k, v)| (&**k, v))
+    }
+
+    pub(crate) fn comments_mut(&mut self, path: &[Cow<'i, str>]) -> &mut ValueComments<'i> {
+        self.comments.entry(dotted_path(path)).or_default()
+    }
+}
+
+/// The comments attached to a value or table header.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValueComments<'i> {
+    pub(crate) leading: Vec<&'i str>,
+    pub(crate) trailing: Option<&'i str>,
+}
+
+impl<'i> ValueComments<'i> {
+    /// The text of the comment lines right above the key or table header, in order.
+    pub fn leading(&self) -> &[&'i str] {
+        &self.leading
+    }
+
+    /// The text of the comment following the value or table header, on the same line.
+    pub fn trailing(&self) -> Option<&'i str> {
+        self.trailing
+    }
+}
+
+/// Format `path` as a dotted key, quoting the keys that can't be bare.
+fn dotted_path(path: &[Cow<'_, str>]) -> String {
+    let mut dotted = String::new();
+    for (i, key) in path.iter().enumerate() {
+        if i != 0 {
+            dotted.push('.');
+        }
+        push_key(&mut dotted, key);
+    }
+
+    dotted
+}
+
+/// Append `key` to `dotted`, quoted if it can't be bare.
+pub(crate) fn push_key(dotted: &mut String, key: &str) {
+    let is_bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_bare {
+        dotted.push_str(key);
+    } else {
+        // Formatting into a `String` can't fail.
+        let _ = write!(dotted, "{}", Value::String(Cow::Borrowed(key)));
+    }
+}
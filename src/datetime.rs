@@ -4,9 +4,10 @@
 //! that it can be drop once `toml_datetime` is updated to support `no_std`, which will require API
 //! breakage.
 
-use alloc::format;
 use core::{
+    cmp::Ordering,
     fmt,
+    ops::RangeInclusive,
     str::{self, FromStr},
 };
 
@@ -76,7 +77,18 @@ use crate::Error;
 /// [Local Date-Time]: https://toml.io/en/v1.0.0#local-date-time
 /// [Local Date]: https://toml.io/en/v1.0.0#local-date
 /// [Local Time]: https://toml.io/en/v1.0.0#local-time
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+///
+/// **Ordering**: the derived `Ord` is structural, not temporal: it compares the date, then the
+/// time, then the offset. So `1979-05-27T07:32:00Z` and `1979-05-27T00:32:00-07:00` compare
+/// unequal, even though they're the same instant. Use [`Datetime::cmp_instant`] to compare
+/// instants in time.
+///
+/// **Serde**: a `Datetime` deserializes from both native TOML datetimes and strings containing
+/// one. It serializes as a struct named `Datetime`, tagged by a private marker field, so that a
+/// TOML serializer can tell it apart from tables and emit a bare TOML datetime. It deserializes
+/// back from that struct. [`Value::Datetime`](crate::Value::Datetime) serializes the same way.
+/// To (de)serialize it as a string in other formats, use [`serde_rfc3339`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub struct Datetime {
     /// Optional date.
@@ -106,8 +118,8 @@ pub struct Datetime {
 /// > ```
 ///
 /// [Local Date]: https://toml.io/en/v1.0.0#local-date
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Date {
     /// Year: four digits
     pub year: u16,
@@ -137,21 +149,24 @@ pub struct Date {
 /// > must be truncated, not rounded.
 ///
 /// [Local Time]: https://toml.io/en/v1.0.0#local-time
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Time {
     /// Hour: 0 to 23
     pub hour: u8,
     /// Minute: 0 to 59
     pub minute: u8,
     /// Second: 0 to {58, 59, 60} (based on leap second rules)
+    ///
+    /// The parser doesn't check if a leap second (60) actually occurred at that time. See
+    /// [`Time::is_leap_second`].
     pub second: u8,
     /// Nanosecond: 0 to `999_999_999`
     pub nanosecond: u32,
 }
 
 /// A parsed TOML time offset
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
 pub enum Offset {
     /// > A suffix which, when applied to a time, denotes a UTC offset of 00:00;
     /// > often spoken "Zulu" from the ICAO phonetic alphabet representation of
@@ -162,12 +177,109 @@ pub enum Offset {
 
     /// Offset between local time and UTC
     Custom {
-        /// Minutes: -`1_440..1_440`
+        /// Minutes: `-1_440..=1_440`
+        ///
+        /// Use [`Offset::from_minutes`] to check the range on construction.
         minutes: i16,
     },
 }
 
+impl Datetime {
+    /// Compare two datetimes in time, if they're comparable.
+    ///
+    /// Unlike the derived `Ord`, this takes the offsets into account, e.g
+    /// `1979-05-27T07:32:00Z` and `1979-05-27T00:32:00-07:00` are equal. Local values are only
+    /// comparable to local values of the same kind, e.g a local date to a local date.
+    ///
+    /// Returns `None` across different kinds, e.g for an offset date-time and a local date-time,
+    /// since there's no meaningful order between them.
+    pub fn cmp_instant(&self, other: &Datetime) -> Option<Ordering> {
+        match (self.offset, other.offset) {
+            (Some(_), Some(_)) => Some(self.utc_instant()?.cmp(&other.utc_instant()?)),
+            (None, None)
+                if self.date.is_some() == other.date.is_some()
+                    && self.time.is_some() == other.time.is_some() =>
+            {
+                Some((self.date, self.time).cmp(&(other.date, other.time)))
+            }
+            _ => None,
+        }
+    }
+
+    /// The seconds since the Unix epoch, whether it's a leap second and the nanoseconds, if this
+    /// is an offset date-time.
+    ///
+    /// A leap second counts as the second before it, so it's ordered after that second but before
+    /// the next one.
+    fn utc_instant(&self) -> Option<(i64, bool, u32)> {
+        let (date, time, offset) = (self.date?, self.time?, self.offset?);
+        let seconds = date.days_since_epoch() * 86_400
+            + i64::from(time.hour) * 3_600
+            + i64::from(time.minute) * 60
+            + i64::from(time.second.min(59))
+            - i64::from(offset.as_minutes()) * 60;
+
+        Some((seconds, time.is_leap_second(), time.nanosecond))
+    }
+}
+
+impl Time {
+    /// Whether this is a leap second, i.e the second is 60 (e.g `23:59:60`).
+    ///
+    /// Many date and time libraries can't represent leap seconds, so converting such a time to
+    /// them will fail. [`Time::without_leap_second`] can be used before the conversion.
+    pub fn is_leap_second(&self) -> bool {
+        self.second == 60
+    }
+
+    /// The time, with a leap second mapped to the last nanosecond of the second before it.
+    ///
+    /// e.g `23:59:60.5` becomes `23:59:59.999999999`. Other times are returned unchanged.
+    pub fn without_leap_second(&self) -> Time {
+        if !self.is_leap_second() {
+            return *self;
+        }
+
+        Time {
+            second: 59,
+            nanosecond: 999_999_999,
+            ..*self
+        }
+    }
+}
+
+impl Date {
+    /// The number of days since 1970-01-01, in the proleptic Gregorian calendar.
+    fn days_since_epoch(&self) -> i64 {
+        // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let month = i64::from(self.month);
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+}
+
 impl Offset {
+    /// The range of valid offsets, in minutes.
+    pub(crate) const MINUTES: RangeInclusive<i16> = (-24 * 60)..=(24 * 60);
+
+    /// Create an offset from minutes, checking that it's within ±24 hours.
+    ///
+    /// An offset of 0 minutes is [`Offset::Z`].
+    pub fn from_minutes(minutes: i16) -> Result<Self, Error> {
+        match minutes {
+            0 => Ok(Offset::Z),
+            minutes if Self::MINUTES.contains(&minutes) => Ok(Offset::Custom { minutes }),
+            _ => Err(Error::Datetime),
+        }
+    }
+
     /// The offset in minutes.
     pub fn as_minutes(&self) -> i16 {
         match *self {
@@ -177,6 +289,17 @@ impl Offset {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Offset {
+    // serialize as an i16.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i16(self.as_minutes())
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for Offset {
     // deserialize as an i16.
@@ -184,10 +307,32 @@ impl<'de> serde::Deserialize<'de> for Offset {
     where
         D: serde::Deserializer<'de>,
     {
-        match i16::deserialize(deserializer)? {
-            0 => Ok(Offset::Z),
-            minutes => Ok(Offset::Custom { minutes }),
-        }
+        let minutes = i16::deserialize(deserializer)?;
+
+        Offset::from_minutes(minutes).map_err(|_| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(minutes.into()),
+                &"an offset between -1440 and 1440 minutes",
+            )
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Datetime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        // The same fields that native datetimes are deserialized from.
+        let mut s = serializer.serialize_struct(crate::serde::DATETIME_NAME, 4)?;
+        s.serialize_field(crate::serde::DATETIME_MARKER, &true)?;
+        s.serialize_field("date", &self.date)?;
+        s.serialize_field("time", &self.time)?;
+        s.serialize_field("offset", &self.offset)?;
+        s.end()
     }
 }
 
@@ -249,8 +394,14 @@ impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
         if self.nanosecond != 0 {
-            let s = format!("{:09}", self.nanosecond);
-            write!(f, ".{}", s.trim_end_matches('0'))?;
+            // Drop the trailing zeros of the fraction, without allocating.
+            let mut nanosecond = self.nanosecond;
+            let mut width = 9;
+            while nanosecond % 10 == 0 {
+                nanosecond /= 10;
+                width -= 1;
+            }
+            write!(f, ".{nanosecond:0width$}")?;
         }
         Ok(())
     }
@@ -450,7 +601,7 @@ impl FromStr for Datetime {
 
                 let total_minutes = sign * (hours * 60 + minutes);
 
-                if !((-24 * 60)..=(24 * 60)).contains(&total_minutes) {
+                if !Offset::MINUTES.contains(&total_minutes) {
                     return Err(Error::Datetime);
                 }
 
@@ -483,6 +634,61 @@ fn digit(chars: &mut str::Chars<'_>) -> Result<u8, Error> {
     }
 }
 
+/// (De)serialize a [`Datetime`] as a string, e.g `"1979-05-27T07:32:00Z"`.
+///
+/// For use with `#[serde(with = "tomling::datetime::serde_rfc3339")]` on [`Datetime`] fields,
+/// when talking to formats without a native datetime type, such as JSON. Deserialization also
+/// accepts native TOML datetimes, so the same type can be used with [`crate::from_str`].
+#[cfg(feature = "serde")]
+pub mod serde_rfc3339 {
+    use core::fmt;
+
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use super::Datetime;
+
+    /// Serialize `datetime` as a string.
+    pub fn serialize<S>(datetime: &Datetime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(datetime)
+    }
+
+    /// Deserialize a [`Datetime`] from a string, or a native TOML datetime.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Datetime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DatetimeVisitor)
+    }
+
+    struct DatetimeVisitor;
+
+    impl<'de> de::Visitor<'de> for DatetimeVisitor {
+        type Value = Datetime;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a TOML datetime or a string containing one")
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Datetime, E>
+        where
+            E: de::Error,
+        {
+            s.parse()
+                .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(s), &self))
+        }
+
+        fn visit_map<A>(self, map: A) -> Result<Datetime, A::Error>
+        where
+            A: de::MapAccess<'de>,
+        {
+            Datetime::deserialize(de::value::MapAccessDeserializer::new(map))
+        }
+    }
+}
+
 #[cfg(feature = "serde")]
 #[cfg(test)]
 mod tests {
@@ -518,4 +724,255 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn serde_datetime_deserialize_from_string() {
+        #[derive(serde::Deserialize)]
+        struct DatetimeTest {
+            native: Datetime,
+            quoted: Datetime,
+            local: Option<Datetime>,
+        }
+
+        let toml = r#"
+            native = 1979-05-27T07:32:00Z
+            quoted = "1979-05-27T07:32:00Z"
+            local = "07:32:00"
+        "#;
+        let t: DatetimeTest = crate::from_str(toml).unwrap();
+        assert_eq!(t.native, t.quoted);
+        assert_eq!(t.local, Some("07:32:00".parse().unwrap()));
+
+        let e = crate::from_str::<DatetimeTest>("native = 1979-05-27\nquoted = \"nope\"");
+        assert!(e.is_err());
+    }
+
+    #[test]
+    fn time_display() {
+        use alloc::string::ToString;
+
+        for (nanosecond, expected) in [
+            (0, "07:32:05"),
+            (1, "07:32:05.000000001"),
+            (500_000_000, "07:32:05.5"),
+            (123_000, "07:32:05.000123"),
+            (999_999_999, "07:32:05.999999999"),
+        ] {
+            let time = Time {
+                hour: 7,
+                minute: 32,
+                second: 5,
+                nanosecond,
+            };
+            assert_eq!(time.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn cmp_instant() {
+        let dt = |s: &str| s.parse::<Datetime>().unwrap();
+
+        assert_eq!(
+            dt("1979-05-27T07:32:00Z").cmp_instant(&dt("1979-05-27T00:32:00-07:00")),
+            Some(Ordering::Equal)
+        );
+        // Structurally, they differ.
+        assert_ne!(dt("1979-05-27T07:32:00Z"), dt("1979-05-27T00:32:00-07:00"));
+        assert_eq!(
+            dt("1979-05-27T07:32:00+01:00").cmp_instant(&dt("1979-05-27T07:00:00Z")),
+            Some(Ordering::Less)
+        );
+        // Across days, months and years.
+        assert_eq!(
+            dt("2000-01-01T00:30:00+01:00").cmp_instant(&dt("1999-12-31T23:30:00Z")),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            dt("2000-03-01T00:00:00.5Z").cmp_instant(&dt("2000-02-29T23:59:59.999-00:01")),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            dt("1970-01-01T00:00:00Z").utc_instant(),
+            Some((0, false, 0))
+        );
+        assert_eq!(
+            dt("0000-01-01T00:00:00Z").cmp_instant(&dt("1969-12-31T23:59:59Z")),
+            Some(Ordering::Less)
+        );
+
+        // Local values are only comparable to the same kind of local values.
+        for (a, b, expected) in [
+            (
+                "1979-05-27T07:32:00",
+                "1979-05-27T07:32:00",
+                Ordering::Equal,
+            ),
+            ("1979-05-27T07:32:00", "1979-05-28T00:00:00", Ordering::Less),
+            ("1979-05-27", "1979-05-26", Ordering::Greater),
+            ("07:32:00", "07:32:00.5", Ordering::Less),
+        ] {
+            assert_eq!(dt(a).cmp_instant(&dt(b)), Some(expected), "{a} {b}");
+        }
+        let kinds = [
+            "1979-05-27T07:32:00Z",
+            "1979-05-27T07:32:00",
+            "1979-05-27",
+            "07:32:00",
+        ];
+        for (i, a) in kinds.iter().enumerate() {
+            for (j, b) in kinds.iter().enumerate() {
+                assert_eq!(dt(a).cmp_instant(&dt(b)).is_some(), i == j, "{a} {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn offset_range() {
+        #[derive(Debug, serde::Deserialize)]
+        struct OffsetTest {
+            offset: Offset,
+        }
+
+        assert_eq!(Offset::from_minutes(0).unwrap(), Offset::Z);
+        assert_eq!(
+            Offset::from_minutes(-1440).unwrap(),
+            Offset::Custom { minutes: -1440 }
+        );
+        Offset::from_minutes(2000).unwrap_err();
+        Offset::from_minutes(-1441).unwrap_err();
+
+        let t: OffsetTest = crate::from_str("offset = 120").unwrap();
+        assert_eq!(t.offset, Offset::Custom { minutes: 120 });
+        let e = crate::from_str::<OffsetTest>("offset = 2000").unwrap_err();
+        assert!(
+            alloc::string::ToString::to_string(&e).contains("2000"),
+            "{e}"
+        );
+    }
+
+    #[test]
+    fn leap_second() {
+        let dt = |s: &str| s.parse::<Datetime>().unwrap();
+
+        let leap = crate::parse("t = 1990-12-31T23:59:60Z").unwrap();
+        let leap = leap.get("t").and_then(crate::Value::as_datetime).unwrap();
+        assert_eq!(leap, dt("1990-12-31T23:59:60Z"));
+        let time = leap.time.unwrap();
+        assert!(time.is_leap_second());
+        assert_eq!(
+            time.without_leap_second(),
+            Time {
+                hour: 23,
+                minute: 59,
+                second: 59,
+                nanosecond: 999_999_999,
+            }
+        );
+        let not_leap = dt("23:59:59.5").time.unwrap();
+        assert!(!not_leap.is_leap_second());
+        assert_eq!(not_leap.without_leap_second(), not_leap);
+
+        // A leap second is ordered between the seconds around it.
+        for (other, expected) in [
+            ("1990-12-31T23:59:59.9Z", Ordering::Greater),
+            ("1990-12-31T23:59:60.5Z", Ordering::Less),
+            ("1991-01-01T00:00:00Z", Ordering::Less),
+            ("1991-01-01T00:59:60+01:00", Ordering::Equal),
+        ] {
+            assert_eq!(leap.cmp_instant(&dt(other)), Some(expected), "{other}");
+        }
+    }
+
+    #[test]
+    fn serde_serialize() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Release {
+            date: Datetime,
+            local: Datetime,
+        }
+
+        let toml = "date = 1979-05-27T00:32:00.999999-07:00\nlocal = 07:32:00";
+        let release = crate::from_str::<Release>(toml).unwrap();
+
+        // Tagged by the marker, with the same fields as deserialized from.
+        let json = serde_json::to_value(&release).unwrap();
+        assert_eq!(
+            json["local"],
+            serde_json::json!({
+                crate::serde::DATETIME_MARKER: true,
+                "date": null,
+                "time": { "hour": 7, "minute": 32, "second": 0, "nanosecond": 0 },
+                "offset": null,
+            })
+        );
+        assert_eq!(json["date"]["offset"], -420);
+
+        // Round-trips, including through `Value`.
+        assert_eq!(
+            serde_json::from_value::<Release>(json.clone()).unwrap(),
+            release
+        );
+        let json = json.to_string();
+        let value = serde_json::from_str::<crate::Value<'_>>(&json).unwrap();
+        assert_eq!(
+            value.get("local").and_then(crate::Value::as_datetime),
+            Some(release.local)
+        );
+        assert_eq!(
+            value.get("date").and_then(crate::Value::as_datetime),
+            Some(release.date)
+        );
+    }
+
+    #[test]
+    fn serde_value_datetime() {
+        #[derive(serde::Serialize)]
+        struct Release {
+            date: Datetime,
+            dates: [Datetime; 2],
+        }
+
+        let value =
+            crate::parse("date = 1979-05-27T07:32:00Z\ndates = [1979-05-27, 07:32:00]").unwrap();
+        let dt = |key| value.get(key).and_then(crate::Value::as_datetime).unwrap();
+        let dates = value.get("dates").and_then(crate::Value::as_array).unwrap();
+        let dates =
+            [dates.get(0), dates.get(1)].map(|dt| dt.and_then(crate::Value::as_datetime).unwrap());
+        let release = Release {
+            date: dt("date"),
+            dates,
+        };
+
+        // A `Value::Datetime` serializes the same as a `Datetime` field.
+        assert_eq!(
+            serde_json::to_value(&value).unwrap(),
+            serde_json::to_value(&release).unwrap()
+        );
+    }
+
+    #[test]
+    fn serde_rfc3339() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Release {
+            #[serde(with = "super::serde_rfc3339")]
+            date: Datetime,
+        }
+
+        let date = "1979-05-27T00:32:00.999999-07:00".parse().unwrap();
+        let json = r#"{"date":"1979-05-27T00:32:00.999999-07:00"}"#;
+        assert_eq!(serde_json::to_string(&Release { date }).unwrap(), json);
+        assert_eq!(
+            serde_json::from_str::<Release>(json).unwrap(),
+            Release { date }
+        );
+        assert!(serde_json::from_str::<Release>(r#"{"date":"nope"}"#).is_err());
+
+        // Both native and quoted datetimes in TOML.
+        for toml in [
+            "date = 1979-05-27T00:32:00.999999-07:00",
+            "date = \"1979-05-27T00:32:00.999999-07:00\"",
+        ] {
+            assert_eq!(crate::from_str::<Release>(toml).unwrap(), Release { date });
+        }
+    }
 }
@@ -1,4 +1,5 @@
-use winnow::error::ContextError;
+use alloc::string::String;
+use winnow::error::{ContextError, StrContext, StrContextValue};
 
 /// The error type of this library.
 #[derive(Debug, Clone, PartialEq)]
@@ -17,9 +18,128 @@ pub enum Error {
         from: &'static str,
         /// The type to which the conversion was attempted.
         to: &'static str,
+        /// The dotted path of the value in the document (e.g `dependencies.foo.version`), if
+        /// known.
+        path: Option<String>,
     },
     /// Invalid date and time encoding.
     Datetime,
+    /// An I/O error occurred while reading a file.
+    ///
+    /// This variant is only available when the `std` feature is enabled.
+    #[cfg(feature = "std")]
+    Io(IoError),
+}
+
+impl Error {
+    /// Whether this is an [`Error::Parse`].
+    pub fn is_parse(&self) -> bool {
+        matches!(self, Error::Parse(_))
+    }
+
+    /// Whether this is a deserialization error.
+    ///
+    /// Always `false` if the `serde` feature isn't enabled.
+    pub fn is_deserialize(&self) -> bool {
+        #[cfg(feature = "serde")]
+        if let Error::Deserialize(_) = self {
+            return true;
+        }
+
+        false
+    }
+
+    /// Whether this is an [`Error::Convert`].
+    pub fn is_convert(&self) -> bool {
+        matches!(self, Error::Convert { .. })
+    }
+
+    /// Whether this is an [`Error::Datetime`].
+    pub fn is_datetime(&self) -> bool {
+        matches!(self, Error::Datetime)
+    }
+
+    /// The dotted path of the value in the document the error occurred at, if known.
+    ///
+    /// Set on conversion and deserialization errors that occur while deserializing a document.
+    ///
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct Package {
+    ///     version: i64,
+    /// }
+    ///
+    /// let e = tomling::from_str::<Package>("version = \"1.0\"").unwrap_err();
+    /// assert_eq!(e.path(), Some("version"));
+    /// # }
+    /// ```
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Error::Convert { path, .. } => path.as_deref(),
+            #[cfg(feature = "serde")]
+            Error::Deserialize(e) => e.path.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Prepend the key of the enclosing table to the path of the error.
+    #[cfg(feature = "serde")]
+    pub(crate) fn with_key(self, key: &str) -> Self {
+        self.with_path_prefix(|path| crate::commented::push_key(path, key))
+    }
+
+    /// Prepend the index in the enclosing array to the path of the error.
+    #[cfg(feature = "serde")]
+    pub(crate) fn with_index(self, index: usize) -> Self {
+        use core::fmt::Write;
+
+        self.with_path_prefix(|path| {
+            // Formatting into a `String` can't fail.
+            let _ = write!(path, "[{index}]");
+        })
+    }
+
+    #[cfg(feature = "serde")]
+    fn with_path_prefix<F>(mut self, prefix: F) -> Self
+    where
+        F: FnOnce(&mut String),
+    {
+        let path = match &mut self {
+            Error::Convert { path, .. } => path,
+            Error::Deserialize(e) => &mut e.path,
+            _ => return self,
+        };
+        let mut prefixed = String::new();
+        prefix(&mut prefixed);
+        if let Some(path) = path.as_deref() {
+            if !path.starts_with('[') {
+                prefixed.push('.');
+            }
+            prefixed.push_str(path);
+        }
+        *path = Some(prefixed);
+
+        self
+    }
+
+    /// The parse error, if this is an [`Error::Parse`].
+    ///
+    /// ```
+    /// use tomling::ParseErrorKind;
+    ///
+    /// let e = tomling::parse("a = \"abc").unwrap_err();
+    /// assert!(e.is_parse());
+    /// let kind = e.as_parse_error().map(|e| e.kind());
+    /// assert_eq!(kind, Some(ParseErrorKind::UnterminatedString));
+    /// ```
+    pub fn as_parse_error(&self) -> Option<&ParseError> {
+        match self {
+            Error::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 // TODO: Implement core::error::Error instead when we can bump the MSRV to 1.81.
@@ -32,6 +152,7 @@ impl std::error::Error for Error {
             Error::Deserialize(d) => Some(d),
             Error::Convert { .. } => None,
             Error::Datetime => None,
+            Error::Io(e) => Some(e),
         }
     }
 }
@@ -42,12 +163,69 @@ impl alloc::fmt::Display for Error {
             Error::Parse(p) => write!(f, "{p}"),
             #[cfg(feature = "serde")]
             Error::Deserialize(s) => write!(f, "{s}"),
-            Error::Convert { from, to } => write!(f, "cannot convert from {from} to {to}"),
+            Error::Convert { from, to, path } => {
+                write!(f, "cannot convert from {from} to {to}")?;
+                match path {
+                    Some(path) => write!(f, " at {path}"),
+                    None => Ok(()),
+                }
+            }
             Error::Datetime => write!(f, "invalid date and time encoding"),
+            #[cfg(feature = "std")]
+            Error::Io(e) => write!(f, "{e}"),
         }
     }
 }
 
+/// The context of the `Error::Io`.
+///
+/// This is only available when the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct IoError {
+    kind: std::io::ErrorKind,
+    message: String,
+    path: std::path::PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl IoError {
+    /// Create a new I/O error for the file at `path`.
+    #[cfg(feature = "cargo-toml")]
+    pub(crate) fn new(e: &std::io::Error, path: &std::path::Path) -> Self {
+        Self {
+            kind: e.kind(),
+            message: alloc::format!("{e}"),
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// The kind of the underlying I/O error.
+    pub fn kind(&self) -> std::io::ErrorKind {
+        self.kind
+    }
+
+    /// The path of the file the error occurred on.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+}
+
+#[cfg(feature = "std")]
+impl alloc::fmt::Display for IoError {
+    fn fmt(&self, f: &mut alloc::fmt::Formatter<'_>) -> alloc::fmt::Result {
+        write!(
+            f,
+            "failed to read {}: {}",
+            self.path.display(),
+            self.message
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IoError {}
+
 /// The context of the `Error::Parse`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParseError {
@@ -59,11 +237,112 @@ impl ParseError {
     pub(crate) fn new(context: ContextError) -> Self {
         Self { context }
     }
+
+    /// The category of the failure.
+    pub fn kind(&self) -> ParseErrorKind {
+        let label = self.context.context().find_map(|c| match c {
+            StrContext::Label(label) => Some(*label),
+            _ => None,
+        });
+
+        match label {
+            Some("string") => ParseErrorKind::UnterminatedString,
+            Some("escape sequence") => ParseErrorKind::InvalidEscape,
+            Some(
+                "integer"
+                | "hexadecimal integer"
+                | "octal integer"
+                | "binary integer"
+                | "floating-point number",
+            ) => ParseErrorKind::InvalidNumber,
+            Some("date-time" | "time" | "time offset") => ParseErrorKind::InvalidDatetime,
+            Some("key") => ParseErrorKind::DuplicateKey,
+            Some("table header" | "array of tables" | "dotted key") => ParseErrorKind::InvalidTable,
+            _ => ParseErrorKind::UnexpectedChar,
+        }
+    }
+
+    /// What the parser expected to find, if known.
+    ///
+    /// Only textual expectations are reported, i.e. an expected single character is not.
+    pub fn expected(&self) -> Option<&str> {
+        self.context.context().find_map(|c| match c {
+            StrContext::Expected(
+                StrContextValue::Description(s) | StrContextValue::StringLiteral(s),
+            ) => Some(*s),
+            _ => None,
+        })
+    }
+}
+
+/// The category of a [`ParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// An unexpected character was encountered.
+    UnexpectedChar,
+    /// A string is missing its closing quote(s).
+    UnterminatedString,
+    /// An invalid escape sequence in a string.
+    InvalidEscape,
+    /// An invalid integer or floating-point number.
+    InvalidNumber,
+    /// An invalid date and/or time.
+    InvalidDatetime,
+    /// A key was defined more than once.
+    DuplicateKey,
+    /// A table, array of tables or dotted key conflicts with an earlier definition.
+    InvalidTable,
 }
 
+/// Renders the same as `ContextError`, without collecting the expectations into a `Vec` first.
 impl alloc::fmt::Display for ParseError {
     fn fmt(&self, f: &mut alloc::fmt::Formatter<'_>) -> alloc::fmt::Result {
-        write!(f, "{}", self.context)
+        let label = self.context.context().find_map(|c| match c {
+            StrContext::Label(label) => Some(label),
+            _ => None,
+        });
+        let mut expected = self
+            .context
+            .context()
+            .filter_map(|c| match c {
+                StrContext::Expected(expected) => Some(expected),
+                _ => None,
+            })
+            .peekable();
+        let mut newline = false;
+
+        if let Some(label) = label {
+            newline = true;
+            write!(f, "invalid {label}")?;
+        }
+
+        if expected.peek().is_some() {
+            if newline {
+                writeln!(f)?;
+            }
+            newline = true;
+
+            f.write_str("expected ")?;
+            for (i, expected) in expected.enumerate() {
+                if i != 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{expected}")?;
+            }
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(cause) = self.context.cause() {
+            if newline {
+                writeln!(f)?;
+            }
+            write!(f, "{cause}")?;
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = newline;
+
+        Ok(())
     }
 }
 
@@ -79,6 +358,7 @@ impl std::error::Error for ParseError {
 #[derive(Debug, Clone, PartialEq)]
 pub struct DeserializeError {
     pub(crate) de: serde::de::value::Error,
+    pub(crate) path: Option<String>,
 }
 
 #[cfg(feature = "serde")]
@@ -86,6 +366,7 @@ impl serde::de::Error for Error {
     fn custom<T: alloc::fmt::Display>(msg: T) -> Self {
         Self::Deserialize(DeserializeError {
             de: serde::de::value::Error::custom(msg),
+            path: None,
         })
     }
 }
@@ -93,14 +374,18 @@ impl serde::de::Error for Error {
 #[cfg(feature = "serde")]
 impl From<serde::de::value::Error> for Error {
     fn from(e: serde::de::value::Error) -> Self {
-        Self::Deserialize(DeserializeError { de: e })
+        Self::Deserialize(DeserializeError { de: e, path: None })
     }
 }
 
 #[cfg(feature = "serde")]
 impl alloc::fmt::Display for DeserializeError {
     fn fmt(&self, f: &mut alloc::fmt::Formatter<'_>) -> alloc::fmt::Result {
-        write!(f, "{}", self.de)
+        write!(f, "{}", self.de)?;
+        match &self.path {
+            Some(path) => write!(f, " at {path}"),
+            None => Ok(()),
+        }
     }
 }
 
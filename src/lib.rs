@@ -14,8 +14,11 @@
 
 extern crate alloc;
 
+#[macro_use]
+mod macros;
+
 mod value;
-pub use value::Value;
+pub use value::{OrderedValue, Value, ValueIndex, ValueKind};
 pub mod table;
 pub use table::Table;
 pub mod array;
@@ -23,7 +26,13 @@ pub use array::Array;
 pub mod datetime;
 pub use datetime::{Date, Datetime, Time};
 mod parse;
-pub use parse::parse;
+pub use parse::{
+    parse, parse_comments, parse_spanned, parse_table_body, parse_with_comments, Comment,
+};
+mod spanned;
+pub use spanned::SpannedTable;
+mod commented;
+pub use commented::{CommentedTable, ValueComments};
 #[cfg(feature = "serde")]
 mod serde;
 #[cfg(feature = "serde")]
@@ -31,4 +40,6 @@ pub use crate::serde::from_str;
 #[cfg(feature = "cargo-toml")]
 pub mod cargo;
 mod error;
-pub use error::{Error, ParseError};
+#[cfg(feature = "std")]
+pub use error::IoError;
+pub use error::{Error, ParseError, ParseErrorKind};
@@ -0,0 +1,168 @@
+//! Macros to build [`Value`](crate::Value) and [`Table`](crate::Table) trees.
+
+/// Build a [`Value`](crate::Value) from TOML-like syntax.
+///
+/// Tables are written as `{ key: value, .. }` and arrays as `[value, ..]`, nested as needed. Keys
+/// are either identifiers or string literals (e.g `"default-features"`). Any other value is
+/// converted with [`Value::from`](crate::Value::from), so it can be a literal, a negative number,
+/// a variable or any expression in parentheses.
+///
+/// ```
+/// use tomling::{toml_value, Value};
+///
+/// let version = "1.0";
+/// let dependency = toml_value!({
+///     version: version,
+///     features: ["derive"],
+///     "default-features": false,
+///     priority: -1,
+/// });
+/// assert_eq!(dependency.get("version").and_then(Value::as_str), Some("1.0"));
+/// assert_eq!(dependency.get("features").unwrap(), &["derive"]);
+/// assert_eq!(dependency.get("default-features"), Some(&Value::Boolean(false)));
+/// assert_eq!(dependency.get("priority"), Some(&Value::Integer(-1)));
+/// ```
+#[macro_export]
+macro_rules! toml_value {
+    (@array [$($elems:expr,)*]) => {{
+        #[allow(unused_mut)]
+        let mut array = $crate::Array::new();
+        $(array.push($elems);)*
+        array
+    }};
+    (@array [$($elems:expr,)*] - $value:tt $(, $($rest:tt)*)?) => {
+        $crate::toml_value!(@array [$($elems,)* $crate::toml_value!(-$value),] $($($rest)*)?)
+    };
+    (@array [$($elems:expr,)*] $value:tt $(, $($rest:tt)*)?) => {
+        $crate::toml_value!(@array [$($elems,)* $crate::toml_value!($value),] $($($rest)*)?)
+    };
+    ({ $($table:tt)* }) => {
+        $crate::Value::Table($crate::toml_table!($($table)*))
+    };
+    ([ $($array:tt)* ]) => {
+        $crate::Value::Array($crate::toml_value!(@array [] $($array)*))
+    };
+    ($value:expr) => {
+        $crate::Value::from($value)
+    };
+}
+
+/// Build a [`Table`](crate::Table) from TOML-like syntax.
+///
+/// The syntax is the same as the contents of a table in [`toml_value!`].
+///
+/// ```
+/// use tomling::{parse, toml_table};
+///
+/// let table = toml_table! {
+///     package: { name: "example", version: "1.0" },
+///     bin: [{ name: "some-binary", path: "src/bin/my-binary.rs" }],
+/// };
+/// let parsed = parse(
+///     r#"
+///     [package]
+///     name = "example"
+///     version = "1.0"
+///
+///     [[bin]]
+///     name = "some-binary"
+///     path = "src/bin/my-binary.rs"
+///     "#,
+/// )
+/// .unwrap();
+/// assert_eq!(table, parsed);
+/// ```
+#[macro_export]
+macro_rules! toml_table {
+    (@key $key:ident) => {
+        stringify!($key)
+    };
+    (@key $key:literal) => {
+        $key
+    };
+    (@insert $table:ident) => {};
+    (@insert $table:ident $key:tt : - $value:tt $(, $($rest:tt)*)?) => {
+        $table.insert(
+            $crate::toml_table!(@key $key).into(),
+            $crate::toml_value!(-$value),
+        );
+        $crate::toml_table!(@insert $table $($($rest)*)?);
+    };
+    (@insert $table:ident $key:tt : $value:tt $(, $($rest:tt)*)?) => {
+        $table.insert(
+            $crate::toml_table!(@key $key).into(),
+            $crate::toml_value!($value),
+        );
+        $crate::toml_table!(@insert $table $($($rest)*)?);
+    };
+    (@insert $table:ident $($rest:tt)*) => {
+        compile_error!("expected comma-separated `key: value` pairs")
+    };
+    ($($tokens:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut table = $crate::Table::new();
+        $crate::toml_table!(@insert table $($tokens)*);
+        table
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::Cow;
+
+    use crate::{Array, Table, Value};
+
+    #[test]
+    fn toml_value() {
+        assert_eq!(toml_value!(1), Value::Integer(1));
+        assert_eq!(toml_value!(-1.5), Value::Float(-1.5));
+        assert_eq!(toml_value!("a"), Value::from("a"));
+        assert_eq!(toml_value!([]), Value::Array(Array::new()));
+        assert_eq!(toml_value!({}), Value::Table(Table::new()));
+
+        let mut inner = Array::new();
+        inner.push(Value::Boolean(true));
+        let mut array = Array::new();
+        array
+            .push(Value::Integer(1))
+            .push(Value::Integer(-2))
+            .push(Value::Array(inner));
+        assert_eq!(toml_value!([1, -2, [true],]), Value::Array(array));
+
+        let name = Cow::Borrowed("tomling");
+        assert_eq!(
+            toml_value!({ name: name, "with space": (2 * 3) }),
+            [("name", Value::from("tomling")), ("with space", 6.into())]
+                .into_iter()
+                .collect::<Value<'_>>()
+        );
+    }
+
+    #[test]
+    fn toml_table() {
+        let table = toml_table! {
+            package: { name: "example", authors: ["Alice", "Bob"] },
+            dependencies: { serde: { version: "1.0", features: ["derive"] } },
+            bin: [{ name: "a" }, { name: "b" }],
+        };
+        let parsed = crate::parse(
+            r#"
+            [package]
+            name = "example"
+            authors = ["Alice", "Bob"]
+
+            [dependencies]
+            serde = { version = "1.0", features = ["derive"] }
+
+            [[bin]]
+            name = "a"
+
+            [[bin]]
+            name = "b"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(table, parsed);
+        assert_eq!(toml_table! {}, Table::new());
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use core::{cell::RefCell, ops::Range};
+use winnow::{stream::Offset as _, Parser};
+
+use super::document::Document;
+use crate::{CommentedTable, Error, ParseError};
+
+/// A comment in a TOML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment<'i> {
+    text: &'i str,
+    span: Range<usize>,
+}
+
+impl<'i> Comment<'i> {
+    /// The text of the comment, after the `#` and without the trailing newline.
+    pub fn text(&self) -> &'i str {
+        self.text
+    }
+
+    /// The byte range of the comment in the document, including the `#`.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Parse a TOML document and collect its comments, in the order they appear.
+///
+/// Unlike [`crate::parse`], which discards comments, this is meant for tools that need to keep
+/// the comments around when rewriting a document.
+///
+/// ```
+/// let toml = "# The name.\nname = \"tomling\" # Not `#` in a string.\n";
+/// let comments = tomling::parse_comments(toml).unwrap();
+/// let texts = comments.iter().map(|c| c.text()).collect::<Vec<_>>();
+/// assert_eq!(texts, [" The name.", " Not `#` in a string."]);
+/// assert_eq!(&toml[comments[1].span()], "# Not `#` in a string.");
+/// ```
+pub fn parse_comments(input: &str) -> Result<Vec<Comment<'_>>, Error> {
+    let mut document = Document::new().with_comments();
+    super::parse_document(&mut document, true)
+        .parse(input)
+        .map_err(|e| ParseError::new(e.into_inner()))
+        .map_err(Error::Parse)?;
+
+    Ok(document.take_comments())
+}
+
+/// Parse a TOML document, along with the comments attached to its values and table headers.
+///
+/// A comment is attached to a key/value pair or a table header either as a leading comment, if
+/// it's on its own line right above it, or as a trailing comment, if it follows it on the same
+/// line (e.g after the closing bracket of a multiline array). Comments separated from the next key
+/// or table header by an empty line, and comments inside multiline arrays are not attached to
+/// anything. For arrays of tables, the comments of all the elements are attached to the same path.
+///
+/// ```
+/// let toml = "# The name.\n# Must be unique.\nname = \"tomling\" # Not `#` in a string.\n";
+/// let commented = tomling::parse_with_comments(toml).unwrap();
+/// let comments = commented.comments("name").unwrap();
+/// assert_eq!(comments.leading(), [" The name.", " Must be unique."]);
+/// assert_eq!(comments.trailing(), Some(" Not `#` in a string."));
+/// ```
+pub fn parse_with_comments(input: &str) -> Result<CommentedTable<'_>, Error> {
+    let mut document = Document::spanned().with_comments();
+    super::parse_document(&mut document, true)
+        .parse(input)
+        .map_err(|e| ParseError::new(e.into_inner()))
+        .map_err(Error::Parse)?;
+    let mut comments = document.take_comments().into_iter().peekable();
+    let (table, spans) = document.into_spanned().into_parts();
+    let mut commented = CommentedTable::new(table);
+
+    let newlines = input
+        .bytes()
+        .enumerate()
+        .filter_map(|(i, b)| (b == b'\n').then_some(i))
+        .collect::<Vec<_>>();
+    let line = |offset: usize| newlines.partition_point(|&n| n < offset);
+    let is_whole_line = |offset: usize| {
+        let start = input[..offset].rfind('\n').map_or(0, |n| n + 1);
+        input[start..offset]
+            .trim_start_matches(super::BOM)
+            .trim_matches(|c| c == ' ' || c == '\t')
+            .is_empty()
+    };
+
+    // The consecutive comment lines seen since the last key or table header.
+    let mut leading = Vec::new();
+    let mut leading_end_line = 0;
+    for (path, span) in spans {
+        while let Some(comment) = comments.next_if(|c| c.span.start < span.start) {
+            let comment_line = line(comment.span.start);
+            let whole_line = is_whole_line(comment.span.start);
+            if !whole_line || (!leading.is_empty() && comment_line != leading_end_line + 1) {
+                leading.clear();
+            }
+            if whole_line {
+                leading.push(comment.text);
+                leading_end_line = comment_line;
+            }
+        }
+        let attached = !leading.is_empty() && leading_end_line + 1 == line(span.start);
+        if attached {
+            commented.comments_mut(&path).leading.append(&mut leading);
+        }
+        leading.clear();
+
+        // Comments inside the value, e.g in a multiline array.
+        while comments.next_if(|c| c.span.start < span.end).is_some() {}
+        let end_line = line(span.end);
+        if let Some(comment) = comments.next_if(|c| line(c.span.start) == end_line) {
+            let comments = commented.comments_mut(&path);
+            comments.trailing = comments.trailing.or(Some(comment.text));
+        }
+    }
+
+    Ok(commented)
+}
+
+/// Records the comments of a document while it's being parsed.
+///
+/// Does nothing unless created with [`CommentRecorder::new`].
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct CommentRecorder<'c, 'i> {
+    recorded: Option<(&'i str, &'c RefCell<Vec<Comment<'i>>>)>,
+}
+
+impl<'c, 'i> CommentRecorder<'c, 'i> {
+    /// Record the comments of `source` into `comments`.
+    pub(super) fn new(source: &'i str, comments: &'c RefCell<Vec<Comment<'i>>>) -> Self {
+        Self {
+            recorded: Some((source, comments)),
+        }
+    }
+
+    /// Record `comment`, a slice of the source including the `#`.
+    pub(super) fn record(&self, comment: &'i str) {
+        let Some((source, comments)) = self.recorded else {
+            return;
+        };
+        let start = comment.offset_from(&source);
+        let mut comments = comments.borrow_mut();
+        // Parsers that backtrack (e.g `separated` after a trailing comma in an array) parse the
+        // same comments again.
+        while comments.last().map_or(false, |c| c.span.start >= start) {
+            comments.pop();
+        }
+        comments.push(Comment {
+            text: &comment[1..],
+            span: start..start + comment.len(),
+        });
+    }
+}
@@ -105,14 +105,10 @@ fn time_offset(input: &mut &str) -> ModalResult<Offset> {
             one_of(('Z', 'z')).value(Offset::Z),
             (one_of(('+', '-')), cut_err((time_hour, ':', time_minute)))
                 .map(|(sign, (hours, _, minutes))| {
-                    let sign = match sign {
-                        '+' => 1,
-                        '-' => -1,
-                        _ => unreachable!("Parser prevents this"),
-                    };
+                    let sign = if sign == '-' { -1 } else { 1 };
                     sign * (hours as i16 * 60 + minutes as i16)
                 })
-                .verify(|minutes| ((-24 * 60)..=(24 * 60)).contains(minutes))
+                .verify(|minutes| Offset::MINUTES.contains(minutes))
                 .map(|minutes| Offset::Custom { minutes }),
         ))
         .context(StrContext::Label("time offset")),
@@ -123,7 +119,7 @@ fn time_offset(input: &mut &str) -> ModalResult<Offset> {
 // date-fullyear  = 4DIGIT
 fn date_fullyear(input: &mut &str) -> ModalResult<u16> {
     unsigned_digits::<4, 4>
-        .map(|s: &str| s.parse::<u16>().expect("4DIGIT should match u8"))
+        .try_map(|s: &str| s.parse::<u16>().map_err(|_| Error::Datetime))
         .parse_next(input)
 }
 
@@ -131,7 +127,7 @@ fn date_fullyear(input: &mut &str) -> ModalResult<u16> {
 fn date_month(input: &mut &str) -> ModalResult<u8> {
     unsigned_digits::<2, 2>
         .try_map(|s: &str| {
-            let d = s.parse::<u8>().expect("2DIGIT should match u8");
+            let d = s.parse::<u8>().map_err(|_| Error::Datetime)?;
             if (1..=12).contains(&d) {
                 Ok(d)
             } else {
@@ -145,7 +141,7 @@ fn date_month(input: &mut &str) -> ModalResult<u8> {
 fn date_mday(input: &mut &str) -> ModalResult<u8> {
     unsigned_digits::<2, 2>
         .try_map(|s: &str| {
-            let d = s.parse::<u8>().expect("2DIGIT should match u8");
+            let d = s.parse::<u8>().map_err(|_| Error::Datetime)?;
             if (1..=31).contains(&d) {
                 Ok(d)
             } else {
@@ -166,7 +162,7 @@ const TIME_DELIM: (u8, u8, u8) = (b'T', b't', b' ');
 fn time_hour(input: &mut &str) -> ModalResult<u8> {
     unsigned_digits::<2, 2>
         .try_map(|s: &str| {
-            let d = s.parse::<u8>().expect("2DIGIT should match u8");
+            let d = s.parse::<u8>().map_err(|_| Error::Datetime)?;
             if (0..=23).contains(&d) {
                 Ok(d)
             } else {
@@ -180,7 +176,7 @@ fn time_hour(input: &mut &str) -> ModalResult<u8> {
 fn time_minute(input: &mut &str) -> ModalResult<u8> {
     unsigned_digits::<2, 2>
         .try_map(|s: &str| {
-            let d = s.parse::<u8>().expect("2DIGIT should match u8");
+            let d = s.parse::<u8>().map_err(|_| Error::Datetime)?;
             if (0..=59).contains(&d) {
                 Ok(d)
             } else {
@@ -194,7 +190,7 @@ fn time_minute(input: &mut &str) -> ModalResult<u8> {
 fn time_second(input: &mut &str) -> ModalResult<u8> {
     unsigned_digits::<2, 2>
         .try_map(|s: &str| {
-            let d = s.parse::<u8>().expect("2DIGIT should match u8");
+            let d = s.parse::<u8>().map_err(|_| Error::Datetime)?;
             if (0..=60).contains(&d) {
                 Ok(d)
             } else {
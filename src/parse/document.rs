@@ -0,0 +1,277 @@
+use super::comments::Comment;
+use crate::{spanned::Spans, Array, SpannedTable, Table, Value};
+
+use alloc::{borrow::Cow, collections::BTreeMap, vec::Vec};
+use core::ops::Range;
+
+/// A parsed line of a TOML document.
+pub(super) enum Line<'i> {
+    /// A `[table]` header.
+    Table(Vec<Cow<'i, str>>),
+    /// An `[[array]]` of tables header.
+    ArrayOfTables(Vec<Cow<'i, str>>),
+    /// A key/value pair.
+    KeyValue(Vec<Cow<'i, str>>, Value<'i>),
+    /// Whitespace or comment line.
+    Empty,
+}
+
+/// A conflict between a line and the preceding lines of a document.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Conflict {
+    /// The label of the offending construct.
+    pub(super) label: &'static str,
+    /// What was expected instead.
+    pub(super) expected: &'static str,
+}
+
+const KEY_CONFLICT: Conflict = Conflict {
+    label: "key",
+    expected: "a key not already defined",
+};
+const DOTTED_KEY_CONFLICT: Conflict = Conflict {
+    label: "dotted key",
+    expected: "a table defined by dotted keys",
+};
+const TABLE_CONFLICT: Conflict = Conflict {
+    label: "table header",
+    expected: "a table not already defined",
+};
+const ARRAY_OF_TABLES_CONFLICT: Conflict = Conflict {
+    label: "array of tables",
+    expected: "an array of tables or an undefined key",
+};
+
+/// How a (non-inline) table came into existence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TableKind {
+    /// Created implicitly as a super-table of a header.
+    Implicit,
+    /// Defined by a `[table]` header.
+    Explicit,
+    /// Created through a dotted key.
+    Dotted,
+    /// An array of tables, created through `[[array]]` headers.
+    ArrayOfTables,
+}
+
+/// The state of a TOML document being built, line by line.
+///
+/// Since only the last element of an array of tables can be extended, the tables are tracked by
+/// their header path and the entries under an array of tables are forgotten every time a new
+/// element is appended to it.
+pub(super) struct Document<'i> {
+    root: Table<'i>,
+    /// The header of the current table section. Empty for the root table.
+    current: Vec<Cow<'i, str>>,
+    /// The kind of each table, keyed by its path.
+    kinds: BTreeMap<Vec<Cow<'i, str>>, TableKind>,
+    /// The spans of the values and tables, in document order, if they're being tracked.
+    spans: Option<Spans<'i>>,
+    /// The comments, in document order, if they're being tracked.
+    comments: Option<Vec<Comment<'i>>>,
+}
+
+impl<'i> Document<'i> {
+    pub(super) fn new() -> Self {
+        Self {
+            root: Table::new(),
+            current: Vec::new(),
+            kinds: BTreeMap::new(),
+            spans: None,
+            comments: None,
+        }
+    }
+
+    /// A document that keeps track of the spans of its values and tables.
+    pub(super) fn spanned() -> Self {
+        Self {
+            spans: Some(Vec::new()),
+            ..Self::new()
+        }
+    }
+
+    /// Keep track of the comments of the document as well.
+    pub(super) fn with_comments(self) -> Self {
+        Self {
+            comments: Some(Vec::new()),
+            ..self
+        }
+    }
+
+    /// Whether the comments of the document are being tracked.
+    pub(super) fn has_comments(&self) -> bool {
+        self.comments.is_some()
+    }
+
+    /// Add the comments found while parsing a line, in the order they appear.
+    pub(super) fn push_comments(&mut self, comments: Vec<Comment<'i>>) {
+        if let Some(all) = &mut self.comments {
+            all.extend(comments);
+        }
+    }
+
+    /// Take the comments of the document, if they're being tracked.
+    pub(super) fn take_comments(&mut self) -> Vec<Comment<'i>> {
+        self.comments.take().unwrap_or_default()
+    }
+
+    /// Add a parsed line, found at `span` in the input, to the document.
+    ///
+    /// For key/value pairs, `span` is the span of the value.
+    pub(super) fn push(&mut self, line: Line<'i>, span: Range<usize>) -> Result<(), Conflict> {
+        let path = match (&line, &self.spans) {
+            (Line::Empty, _) | (_, None) => None,
+            (Line::Table(header) | Line::ArrayOfTables(header), Some(_)) => Some(header.clone()),
+            (Line::KeyValue(keys, _), Some(_)) => Some([&self.current[..], keys].concat()),
+        };
+        match line {
+            Line::Table(header) => self.push_table(header)?,
+            Line::ArrayOfTables(header) => self.push_array_of_tables(header)?,
+            Line::KeyValue(keys, value) => self.push_key_value(keys, value)?,
+            Line::Empty => (),
+        }
+        if let (Some(spans), Some(path)) = (&mut self.spans, path) {
+            spans.push((path, span));
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn into_table(self) -> Table<'i> {
+        self.root
+    }
+
+    pub(super) fn into_spanned(self) -> SpannedTable<'i> {
+        SpannedTable::new(self.root, self.spans.unwrap_or_default())
+    }
+
+    fn push_table(&mut self, header: Vec<Cow<'i, str>>) -> Result<(), Conflict> {
+        let (last, parents) = header.split_last().ok_or(TABLE_CONFLICT)?;
+        let Self { root, kinds, .. } = self;
+        let table = super_table(root, kinds, &header, parents).ok_or(TABLE_CONFLICT)?;
+        match table.get(last) {
+            None => {
+                table.insert(last.clone(), Table::new().into());
+                kinds.insert(header.clone(), TableKind::Explicit);
+            }
+            // Only a table created implicitly by a previous header can be defined later on.
+            Some(Value::Table(_)) => match kinds.get_mut(&header) {
+                Some(kind @ TableKind::Implicit) => *kind = TableKind::Explicit,
+                _ => return Err(TABLE_CONFLICT),
+            },
+            // Includes arrays of tables: `[a]` can not follow `[[a]]`.
+            Some(_) => return Err(TABLE_CONFLICT),
+        }
+        self.current = header;
+
+        Ok(())
+    }
+
+    fn push_array_of_tables(&mut self, header: Vec<Cow<'i, str>>) -> Result<(), Conflict> {
+        let (last, parents) = header.split_last().ok_or(ARRAY_OF_TABLES_CONFLICT)?;
+        let Self { root, kinds, .. } = self;
+        let table = super_table(root, kinds, &header, parents).ok_or(ARRAY_OF_TABLES_CONFLICT)?;
+        match table.get_mut(last) {
+            None => {
+                let array = [Value::from(Table::new())]
+                    .into_iter()
+                    .collect::<Array<'_>>();
+                table.insert(last.clone(), array.into());
+                kinds.insert(header.clone(), TableKind::ArrayOfTables);
+            }
+            Some(Value::Array(array)) if kinds.get(&header) == Some(&TableKind::ArrayOfTables) => {
+                array.push(Table::new().into());
+                // The tables in the previous element can't be reached anymore.
+                kinds.retain(|path, _| path.len() <= header.len() || !path.starts_with(&header));
+            }
+            // Neither static arrays nor any other values can be extended.
+            Some(_) => return Err(ARRAY_OF_TABLES_CONFLICT),
+        }
+        self.current = header;
+
+        Ok(())
+    }
+
+    fn push_key_value(
+        &mut self,
+        keys: Vec<Cow<'i, str>>,
+        value: Value<'i>,
+    ) -> Result<(), Conflict> {
+        let (last, parents) = keys.split_last().ok_or(KEY_CONFLICT)?;
+        let mut path = self.current.clone();
+        let Self { root, kinds, .. } = self;
+        let mut table = resolve(root, kinds, &path).ok_or(KEY_CONFLICT)?;
+        for key in parents {
+            path.push(key.clone());
+            let entry = table.entry(key.clone()).or_insert_with(|| {
+                kinds.insert(path.clone(), TableKind::Dotted);
+                Table::new().into()
+            });
+            table = match (entry, kinds.get(&path)) {
+                (Value::Table(table), Some(TableKind::Dotted)) => table,
+                // Dotted keys can't extend tables created by headers, inline tables or arrays, not
+                // even arrays of tables.
+                _ => return Err(DOTTED_KEY_CONFLICT),
+            };
+        }
+        if table.get(last).is_some() {
+            return Err(KEY_CONFLICT);
+        }
+        table.insert(last.clone(), value);
+
+        Ok(())
+    }
+}
+
+/// Walk `parents` (the super-tables of `header`), creating implicit tables as needed.
+fn super_table<'t, 'i>(
+    root: &'t mut Table<'i>,
+    kinds: &mut BTreeMap<Vec<Cow<'i, str>>, TableKind>,
+    header: &[Cow<'i, str>],
+    parents: &[Cow<'i, str>],
+) -> Option<&'t mut Table<'i>> {
+    let mut table = root;
+    for (i, key) in parents.iter().enumerate() {
+        let path = &header[..=i];
+        let entry = table.entry(key.clone()).or_insert_with(|| {
+            kinds.insert(path.to_vec(), TableKind::Implicit);
+            Table::new().into()
+        });
+        table = descend(entry, kinds.get(path))?;
+    }
+
+    Some(table)
+}
+
+/// Resolve the table at `path`, going into the last element of arrays of tables.
+fn resolve<'t, 'i>(
+    root: &'t mut Table<'i>,
+    kinds: &BTreeMap<Vec<Cow<'i, str>>, TableKind>,
+    path: &[Cow<'i, str>],
+) -> Option<&'t mut Table<'i>> {
+    let mut table = root;
+    for (i, key) in path.iter().enumerate() {
+        let entry = table.get_mut(key)?;
+        table = descend(entry, kinds.get(&path[..=i]))?;
+    }
+
+    Some(table)
+}
+
+/// Get the table a header can extend through `value`.
+fn descend<'t, 'i>(
+    value: &'t mut Value<'i>,
+    kind: Option<&TableKind>,
+) -> Option<&'t mut Table<'i>> {
+    match (value, kind) {
+        // Inline tables have no kind, since they're closed.
+        (Value::Table(table), Some(_)) => Some(table),
+        (Value::Array(array), Some(TableKind::ArrayOfTables)) => match array.last_mut() {
+            Some(Value::Table(table)) => Some(table),
+            _ => None,
+        },
+        // Static arrays and other values can't be extended.
+        _ => None,
+    }
+}
@@ -1,63 +1,117 @@
+use super::comments::CommentRecorder;
+
 use winnow::{
-    ascii::space0,
-    combinator::{alt, empty, eof, fail, opt, peek, preceded},
+    combinator::{alt, cut_err, empty, eof, fail, opt, repeat},
     dispatch,
+    error::{StrContext, StrContextValue},
     stream::Stream as _,
     token::{any, one_of, take_while},
     ModalResult, Parser,
 };
 
-/// Parse a comment, w/o the trailing newline.
-pub(crate) fn parse_comment(input: &mut &str) -> ModalResult<()> {
-    preceded(
-        '#',
-        take_while(
-            0..,
-            // > Control characters other than tab (U+0000 to U+0008, U+000A to U+001F, U+007F) are
-            // > not permitted in comments.
-            |c| !matches!(c, '\0'..='\u{08}' | '\u{0a}'..='\u{1f}' | '\u{7f}'),
-        ),
-    )
-    .void()
-    .parse_next(input)
+/// TOML whitespace: `wschar = %x20 / %x09`, i.e only space and tab.
+const WSCHAR: (char, char) = (' ', '\t');
+
+/// Parse optional whitespace, w/o newlines.
+pub(crate) fn ws0<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
+    take_while(0.., WSCHAR).parse_next(input)
 }
 
-/// Parses a comment and newline (unless at EOF).
-pub(crate) fn parse_comment_newline(input: &mut &str) -> ModalResult<()> {
-    (parse_comment, alt((newline, eof.void())))
-        .void()
-        .parse_next(input)
+/// Parse at least one whitespace character, w/o newlines.
+pub(crate) fn ws1<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
+    take_while(1.., WSCHAR).parse_next(input)
 }
 
-/// Parse all whitespace (including newlines) and comments.
-pub(crate) fn parse_whitespace_n_comments(input: &mut &str) -> ModalResult<()> {
-    let mut start = input.checkpoint();
-    loop {
-        let _ = space0.parse_next(input)?;
+/// Parse a comment, w/o the trailing newline, returning it along with the `#`.
+pub(crate) fn parse_comment<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
+    let comment = *input;
+    '#'.parse_next(input)?;
+    let len = input
+        .bytes()
+        // > Control characters other than tab (U+0000 to U+0008, U+000A to U+001F, U+007F) are
+        // > not permitted in comments.
+        //
+        // Bytes of multi-byte characters are all >= 0x80, so `len` is on a char boundary.
+        .take_while(|b| !matches!(b, b'\0'..=b'\x08' | b'\x0a'..=b'\x1f' | b'\x7f'))
+        .count();
+    input.next_slice(len);
+
+    Ok(&comment[..1 + len])
+}
+
+/// Parses a comment and newline (unless at EOF), passing the comment to `comments`.
+pub(crate) fn parse_comment_newline<'c, 'i>(
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<()> + 'c {
+    move |input| {
+        let comment = parse_comment.parse_next(input)?;
+        comments.record(comment);
+
+        alt((newline, eof.void())).parse_next(input)
+    }
+}
 
-        let next_token = opt(peek(any)).parse_next(input)?;
-        match next_token {
-            Some('#') => (parse_comment, newline).void().parse_next(input)?,
-            Some('\n') => (newline).void().parse_next(input)?,
-            Some('\r') => (newline).void().parse_next(input)?,
-            _ => break,
+/// Parses the end of a key/value or table header line: whitespace, an optional comment and a
+/// newline (unless at EOF). The comment is passed to `comments`.
+pub(crate) fn parse_line_end<'c, 'i>(
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<()> + 'c {
+    move |input| {
+        ws0.parse_next(input)?;
+        if let Some(comment) = opt(parse_comment).parse_next(input)? {
+            comments.record(comment);
         }
 
-        let end = input.checkpoint();
-        if start == end {
-            break;
+        alt((newline, eof.void())).parse_next(input)
+    }
+}
+
+/// Parse all whitespace (including newlines) and comments, passing the comments to `comments`.
+///
+/// This is on the hot path of arrays, so runs of whitespace and line feeds are skipped in one go,
+/// only falling back to parsers for comments and carriage returns.
+pub(crate) fn parse_whitespace_n_comments<'c, 'i>(
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<()> + 'c {
+    move |input| loop {
+        let len = input
+            .bytes()
+            .take_while(|b| matches!(b, b' ' | b'\t' | b'\n'))
+            .count();
+        // All skipped bytes are ASCII, so `len` is on a char boundary.
+        input.next_slice(len);
+
+        match input.as_bytes().first() {
+            Some(b'#') => {
+                let comment = parse_comment.parse_next(input)?;
+                comments.record(comment);
+                newline.parse_next(input)?;
+            }
+            Some(b'\r') => newline.parse_next(input)?,
+            _ => return Ok(()),
         }
-        start = end;
     }
+}
 
-    Ok(())
+/// Parse all whitespace, including newlines.
+///
+/// Unlike `multiws0`, a bare carriage return is an error, not whitespace.
+pub(crate) fn parse_whitespace_n_newlines(input: &mut &str) -> ModalResult<()> {
+    repeat(0.., alt((ws1.void(), newline))).parse_next(input)
 }
 
 /// Parse a newline.
+///
+/// A carriage return is only allowed as part of a CRLF newline, so a bare one is a cut error.
 pub(crate) fn newline(input: &mut &str) -> ModalResult<()> {
     dispatch! {any;
         '\n' => empty,
-        '\r' => one_of('\n').void(),
+        '\r' => cut_err(one_of('\n'))
+            .void()
+            .context(StrContext::Label("newline"))
+            .context(StrContext::Expected(StrContextValue::Description(
+                "a line feed after a carriage return",
+            ))),
         _ => fail,
     }
     .parse_next(input)
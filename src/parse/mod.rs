@@ -1,80 +1,207 @@
+mod comments;
 mod datetime;
+mod document;
 mod ignored;
 mod numbers;
 mod strings;
 
-use crate::{Array, Error, ParseError, Table, Value};
+pub use comments::{parse_comments, parse_with_comments, Comment};
 
-use alloc::{borrow::Cow, vec, vec::Vec};
-use ignored::{parse_comment_newline, parse_whitespace_n_comments};
+use crate::{Array, Error, ParseError, SpannedTable, Table, Value};
+
+use alloc::{borrow::Cow, vec::Vec};
+use comments::CommentRecorder;
+use core::cell::RefCell;
+use document::{Document, Line};
+use ignored::{
+    newline, parse_comment_newline, parse_line_end, parse_whitespace_n_comments,
+    parse_whitespace_n_newlines, ws0, ws1,
+};
 use winnow::{
-    ascii::{multispace1, space0},
-    combinator::{alt, cut_err, delimited, opt, peek, preceded, repeat, separated, separated_pair},
-    error::ContextError,
-    token::take_while,
+    combinator::{
+        alt, cut_err, delimited, dispatch, fail, opt, peek, preceded, separated, separated_pair,
+        terminated,
+    },
+    error::{AddContext, ContextError, ErrMode, StrContext, StrContextValue},
+    stream::{Offset as _, Stream as _},
+    token::{any, take_while},
     ModalResult, Parser,
 };
 
 /// Parse a TOML document.
+///
+/// This never panics: any input that isn't a valid TOML document, including values nested
+/// more than 100 levels deep in arrays and inline tables, results in an [`Error::Parse`].
+///
+/// A TOML document is always a table, so a standalone value (e.g `[1, 2, 3]`) isn't a valid
+/// document. Use the [`FromStr`](core::str::FromStr) implementation of [`Value`] to parse one.
 pub fn parse(input: &str) -> Result<Table<'_>, Error> {
-    if input.is_empty() {
-        return Ok(Table::new());
-    }
-    let key_value = parse_key_value.map(|(keys, value)| (None, keys, value));
-    let table_header = parse_table_header
-        .map(|(header, is_array)| (Some((header, is_array)), Vec::new(), Table::new().into()));
-    let whitespace = multispace1.map(|_| (None, Vec::new(), Table::new().into()));
-    let comment_line = parse_comment_newline.map(|_| (None, Vec::new(), Table::new().into()));
-    let line_parser = alt((table_header, key_value, whitespace, comment_line));
-
-    repeat(1.., line_parser)
-        .fold(
-            || (None, Table::new()),
-            |(mut current_table, mut map), (header, keys, value)| {
-                if let Some((header, is_array)) = header {
-                    if is_array {
-                        // Handle array of tables ([[table]])
-                        let key = header.last().expect("Header should not be empty").clone();
-                        let entry = map
-                            .entry(key.clone())
-                            .or_insert_with(|| Array::new().into());
-                        if let Value::Array(array) = entry {
-                            // Append a new empty table to the array
-                            let new_table = Table::new();
-                            array.push(new_table.into());
-
-                            // Update current_table to reference the new table
-                            current_table = Some(vec![key]);
-                        }
-                    } else {
-                        // Handle regular table ([table]) with dotted keys
-                        current_table = Some(header);
-                    }
-                } else if !keys.is_empty() {
-                    if let Some(ref table) = current_table {
-                        if let Some(Value::Array(array)) = map.get_mut(&table[0]) {
-                            // Insert into the most recent table in the array
-                            if let Some(Value::Table(last_table)) = array.last_mut() {
-                                insert_nested_key(last_table, &keys, value);
-                            }
-                        } else {
-                            // Insert into a regular table
-                            let mut full_key = table.clone();
-                            full_key.extend(keys);
-                            insert_nested_key(&mut map, &full_key, value);
-                        }
-                    } else {
-                        // Global key-value pair
-                        insert_nested_key(&mut map, &keys, value);
-                    }
-                }
-                (current_table, map)
-            },
-        )
-        .map(|(_, map)| map)
+    let mut document = Document::new();
+    parse_document(&mut document, true)
+        .parse(input)
+        .map_err(|e| ParseError::new(e.into_inner()))
+        .map_err(Error::Parse)?;
+
+    Ok(document.into_table())
+}
+
+/// Parse the body of a single table, i.e key/value pairs without any table headers.
+///
+/// Useful for fragments that represent the inside of a table, e.g a dependency specification
+/// pasted by a user. A table header (e.g `[package]`) in `input` results in an [`Error::Parse`].
+///
+/// ```
+/// let table = tomling::parse_table_body("version = \"1.0\"\nfeatures = [\"derive\"]").unwrap();
+/// assert_eq!(table.get("version").unwrap(), "1.0");
+/// assert!(tomling::parse_table_body("[dependencies]\nserde = \"1.0\"").is_err());
+/// ```
+pub fn parse_table_body(input: &str) -> Result<Table<'_>, Error> {
+    let mut document = Document::new();
+    parse_document(&mut document, false)
+        .parse(input)
+        .map_err(|e| ParseError::new(e.into_inner()))
+        .map_err(Error::Parse)?;
+
+    Ok(document.into_table())
+}
+
+/// Parse a TOML document, keeping track of where its values are defined in `input`.
+///
+/// This is slower than [`parse`], so only use it if you need the spans, e.g for error reporting.
+///
+/// ```
+/// let toml = "[package]\nname = \"tomling\"\n";
+/// let spanned = tomling::parse_spanned(toml).unwrap();
+/// assert_eq!(&toml[spanned.span(&["package", "name"]).unwrap()], "\"tomling\"");
+/// assert_eq!(&toml[spanned.span(&["package"]).unwrap()], "[package]");
+/// ```
+pub fn parse_spanned(input: &str) -> Result<SpannedTable<'_>, Error> {
+    let mut document = Document::spanned();
+    parse_document(&mut document, true)
         .parse(input)
         .map_err(|e| ParseError::new(e.into_inner()))
-        .map_err(Error::Parse)
+        .map_err(Error::Parse)?;
+
+    Ok(document.into_spanned())
+}
+
+/// Parse a standalone TOML value, surrounded by optional whitespace.
+pub(crate) fn parse_standalone_value(input: &str) -> Result<Value<'_>, Error> {
+    delimited(
+        parse_whitespace_n_newlines,
+        parse_value,
+        parse_whitespace_n_newlines,
+    )
+    .parse(input)
+    .map_err(|e| ParseError::new(e.into_inner()))
+    .map_err(Error::Parse)
+}
+
+/// Parses a TOML document, line by line, into `document`.
+///
+/// A leading byte order mark is skipped. If `headers` is `false`, table headers are an error.
+fn parse_document<'i, 'd>(
+    document: &'d mut Document<'i>,
+    headers: bool,
+) -> impl FnMut(&mut &'i str) -> ModalResult<(), ContextError> + 'd {
+    move |input| parse_lines(input, document, headers)
+}
+
+/// The UTF-8 byte order mark, that some editors put at the start of files.
+const BOM: char = '\u{FEFF}';
+
+/// Parses the lines of a TOML document into `document`.
+fn parse_lines<'i>(
+    input: &mut &'i str,
+    document: &mut Document<'i>,
+    headers: bool,
+) -> ModalResult<()> {
+    let source = *input;
+    opt(BOM).parse_next(input)?;
+    let recorded = RefCell::new(Vec::new());
+    let comments = match document.has_comments() {
+        true => CommentRecorder::new(source, &recorded),
+        false => CommentRecorder::default(),
+    };
+    let span = move |taken: &'i str| {
+        let start = taken.offset_from(&source);
+        start..start + taken.len()
+    };
+    let line_end = || {
+        cut_err(parse_line_end(comments))
+            .context(StrContext::Label("end of line"))
+            .context(StrContext::Expected(StrContextValue::Description(
+                "a newline or a comment",
+            )))
+    };
+    let key_value = terminated(parse_key_value(comments), line_end())
+        .map(|(keys, value, taken)| (Line::KeyValue(keys, value), span(taken)));
+    let table_header = terminated(parse_table_header.with_taken(), line_end()).map(
+        |((header, is_array), taken)| match is_array {
+            true => (Line::ArrayOfTables(header), span(taken)),
+            false => (Line::Table(header), span(taken)),
+        },
+    );
+    let whitespace = alt((ws1.void(), newline)).map(|_| (Line::Empty, 0..0));
+    let comment_line = parse_comment_newline(comments).map(|_| (Line::Empty, 0..0));
+    let mut line_parser = alt((table_header, key_value, whitespace, comment_line));
+
+    while !input.is_empty() {
+        let start = input.checkpoint();
+        let (line, span) = match line_parser.parse_next(input) {
+            Err(ErrMode::Backtrack(e)) => {
+                input.reset(&start);
+                return Err(standalone_value_error(input, e));
+            }
+            line => line?,
+        };
+        if !headers && matches!(line, Line::Table(_) | Line::ArrayOfTables(_)) {
+            input.reset(&start);
+            let expected = StrContextValue::Description("a key, not a table header");
+            let e = ContextError::new()
+                .add_context(input, &start, StrContext::Label("table header"))
+                .add_context(input, &start, StrContext::Expected(expected));
+            return Err(ErrMode::Cut(e));
+        }
+        if let Err(conflict) = document.push(line, span) {
+            input.reset(&start);
+            let expected = StrContextValue::Description(conflict.expected);
+            let e = ContextError::new()
+                .add_context(input, &start, StrContext::Label(conflict.label))
+                .add_context(input, &start, StrContext::Expected(expected));
+            return Err(ErrMode::Cut(e));
+        }
+        document.push_comments(recorded.take());
+    }
+
+    Ok(())
+}
+
+/// Turn `e` into a clearer error if `input` is a standalone value (e.g `[1, 2, 3]`), rather than a
+/// line of a document.
+fn standalone_value_error(input: &mut &str, e: ContextError) -> ErrMode<ContextError> {
+    let start = input.checkpoint();
+    let expected = match parse_value.parse_next(input) {
+        Ok(Value::Array(_)) => "a key or a table header, found an array",
+        Ok(_) => "a key or a table header, found a value",
+        Err(_) if input.starts_with(|c: char| c.is_ascii_control()) => {
+            "a key, a table header or a comment, found a control character"
+        }
+        Err(_) => {
+            input.reset(&start);
+            return ErrMode::Backtrack(e);
+        }
+    };
+    input.reset(&start);
+    let e = ContextError::new()
+        .add_context(input, &start, StrContext::Label("document"))
+        .add_context(
+            input,
+            &start,
+            StrContext::Expected(StrContextValue::Description(expected)),
+        );
+
+    ErrMode::Cut(e)
 }
 
 /// Parses a table header (e.g., `[dependencies]`)
@@ -88,11 +215,23 @@ fn parse_table_header<'i>(
     .parse_next(input)
 }
 
-/// Parses a single key-value pair
-fn parse_key_value<'i>(
-    input: &mut &'i str,
-) -> ModalResult<(Vec<Cow<'i, str>>, Value<'i>), ContextError> {
-    separated_pair(parse_dotted_key, '=', parse_value).parse_next(input)
+/// The keys and value of a key/value pair, along with the source of the value.
+type KeyValue<'i> = (Vec<Cow<'i, str>>, Value<'i>, &'i str);
+
+/// Parses a single key-value pair, along with the source of the value.
+///
+/// The comments inside the value (e.g in a multiline array) are passed to `comments`.
+fn parse_key_value<'c, 'i>(
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<KeyValue<'i>, ContextError> + 'c {
+    move |input| {
+        let value = parse_nested_value(0, comments).with_taken();
+        separated_pair(parse_dotted_key, '=', value)
+            .map(|(keys, (value, taken))| {
+                (keys, value, taken.trim_matches(|c| c == ' ' || c == '\t'))
+            })
+            .parse_next(input)
+    }
 }
 
 /// Parses a dotted or single key
@@ -104,40 +243,74 @@ fn parse_dotted_key<'i>(input: &mut &'i str) -> ModalResult<Vec<Cow<'i, str>>, C
 fn parse_key<'i>(input: &mut &'i str) -> ModalResult<Cow<'i, str>, ContextError> {
     // We don't use `parse_string` here beecause that also accept multiline strings and we don't
     // want that here.
-    let string_key = alt((strings::parse_basic, strings::parse_literal)).map(|s| match s {
-        Value::String(s) => s,
-        _ => unreachable!(),
-    });
     delimited(
-        space0,
+        ws0,
         alt((
-            string_key,
-            take_while(1.., |c: char| c.is_alphanumeric() || c == '_' || c == '-').map(Into::into),
+            strings::parse_basic,
+            strings::parse_literal,
+            take_while(1.., |c: char| {
+                c.is_ascii_alphanumeric() || c == '_' || c == '-'
+            })
+            .map(Into::into),
         )),
-        space0,
+        ws0,
     )
     .parse_next(input)
 }
 
 /// Parses a value (string, integer, float, boolean, array, or table)
 fn parse_value<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
-    delimited(
-        space0,
-        // FIXME: Use `dispatch!` to make it more efficient.
-        alt((
-            strings::parse,
-            parse_datetime,
-            parse_float,
-            parse_integer,
-            parse_boolean,
-            parse_array,
-            parse_inline_table,
-        )),
-        space0,
-    )
-    .parse_next(input)
+    parse_nested_value(0, CommentRecorder::default()).parse_next(input)
+}
+
+/// Parses a value, nested in `depth` arrays and inline tables.
+///
+/// The comments inside the value are passed to `comments`.
+fn parse_nested_value<'c, 'i>(
+    depth: usize,
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<Value<'i>, ContextError> + 'c {
+    move |input| {
+        delimited(
+            ws0,
+            dispatch! {peek(any);
+                '"' | '\'' => strings::parse,
+                '[' => parse_array(depth, comments),
+                '{' => parse_inline_table(depth, comments),
+                't' | 'f' => parse_boolean,
+                // The trailing `fail` drops the context of the number parsers, if neither of them
+                // matches, e.g for `nope`.
+                '0'..='9' => parse_unsigned_number_or_datetime,
+                '+' | '-' | '.' | 'i' | 'n' => alt((parse_float, parse_integer, fail)),
+                _ => fail,
+            },
+            ws0,
+        )
+        .parse_next(input)
+    }
+}
+
+/// Fails if values nested in `depth` arrays and inline tables can't nest any deeper.
+///
+/// Without a limit, deeply nested input would overflow the stack.
+fn parse_nesting<'i>(depth: usize) -> impl FnMut(&mut &'i str) -> ModalResult<(), ContextError> {
+    move |input| {
+        if depth < MAX_NESTING {
+            return Ok(());
+        }
+
+        cut_err(fail)
+            .context(StrContext::Label("value"))
+            .context(StrContext::Expected(StrContextValue::Description(
+                "less deeply nested arrays and inline tables",
+            )))
+            .parse_next(input)
+    }
 }
 
+/// The maximum nesting depth of arrays and inline tables.
+const MAX_NESTING: usize = 100;
+
 /// Parses an integer value
 fn parse_integer<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
     numbers::integer(input).map(Into::into)
@@ -153,65 +326,140 @@ fn parse_boolean<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError
     numbers::boolean(input).map(Into::into)
 }
 
+/// Parses a value starting with a digit: a date-time, a float or an integer.
+///
+/// Which one is decided by the character following the leading digits, instead of trying each
+/// parser in turn: date-times start with a 4-digit year followed by `-` or a 2-digit hour followed
+/// by `:`, and floats have a fractional part or an exponent.
+fn parse_unsigned_number_or_datetime<'i>(
+    input: &mut &'i str,
+) -> ModalResult<Value<'i>, ContextError> {
+    let bytes = input.as_bytes();
+    let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    let int_part = bytes
+        .iter()
+        .take_while(|b| b.is_ascii_digit() || **b == b'_')
+        .count();
+
+    match (digits, bytes.get(digits), bytes.get(int_part)) {
+        (4, Some(b'-'), _) | (2, Some(b':'), _) => parse_datetime(input),
+        (_, _, Some(b'.' | b'e' | b'E')) => parse_float(input),
+        _ => parse_integer(input),
+    }
+}
+
 /// Parses a datatime value.
 fn parse_datetime<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
     datetime::date_time(input).map(Into::into)
 }
 
 /// Parses an array of values
-fn parse_array<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
-    delimited('[', cut_err(parse_multiline_array_values), cut_err(']'))
+fn parse_array<'c, 'i>(
+    depth: usize,
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<Value<'i>, ContextError> + 'c {
+    move |input| {
+        delimited(
+            ('[', parse_nesting(depth)),
+            cut_err(parse_multiline_array_values(depth + 1, comments)),
+            cut_err(']'),
+        )
         .map(Into::into)
         .parse_next(input)
+    }
 }
 
-fn parse_multiline_array_values<'i>(input: &mut &'i str) -> ModalResult<Array<'i>, ContextError> {
-    if peek(opt(']')).parse_next(input)?.is_some() {
-        // Optimize for empty arrays, avoiding `value` from being expected to fail
-        return Ok(Array::new());
-    }
+fn parse_multiline_array_values<'c, 'i>(
+    depth: usize,
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<Array<'i>, ContextError> + 'c {
+    move |input| {
+        if peek(opt(']')).parse_next(input)?.is_some() {
+            // Optimize for empty arrays, avoiding `value` from being expected to fail
+            return Ok(Array::new());
+        }
 
-    let array: Array<'i> = separated(0.., parse_multiline_array_value, ',').parse_next(input)?;
+        let array: Array<'i> =
+            separated(0.., parse_multiline_array_value(depth, comments), ',').parse_next(input)?;
 
-    if !array.is_empty() {
-        // Ignore trailing comma, if present.
-        opt(',').void().parse_next(input)?;
-    }
+        if !array.is_empty() {
+            // Ignore trailing comma, if present.
+            opt(',').void().parse_next(input)?;
+        }
 
-    parse_whitespace_n_comments.void().parse_next(input)?;
+        parse_whitespace_n_comments(comments).parse_next(input)?;
 
-    Ok(array)
+        Ok(array)
+    }
 }
 
-fn parse_multiline_array_value<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
-    preceded(parse_whitespace_n_comments, parse_value).parse_next(input)
+fn parse_multiline_array_value<'c, 'i>(
+    depth: usize,
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<Value<'i>, ContextError> + 'c {
+    move |input| {
+        preceded(
+            parse_whitespace_n_comments(comments),
+            parse_nested_value(depth, comments),
+        )
+        .parse_next(input)
+    }
 }
 
 /// Parses an inline table
-fn parse_inline_table<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
-    delimited(
-        '{',
-        separated(0.., separated_pair(parse_key, '=', parse_value), ','),
-        '}',
-    )
-    .map(|pairs: Vec<(Cow<'i, str>, Value<'i>)>| pairs.into_iter().collect())
-    .parse_next(input)
+fn parse_inline_table<'c, 'i>(
+    depth: usize,
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<Value<'i>, ContextError> + 'c {
+    move |input| {
+        delimited(
+            ('{', parse_nesting(depth)),
+            parse_inline_table_entries(depth + 1, comments),
+            '}',
+        )
+        .map(|pairs: Vec<(Cow<'i, str>, Value<'i>)>| pairs.into_iter().collect())
+        .parse_next(input)
+    }
 }
 
-/// Inserts a value into a nested map using a dotted key
-fn insert_nested_key<'a>(map: &mut Table<'a>, keys: &[Cow<'a, str>], value: Value<'a>) {
-    if let Some((first, rest)) = keys.split_first() {
-        if rest.is_empty() {
-            map.insert(first.clone(), value);
-        } else {
-            let entry = map
-                .entry(first.clone())
-                .or_insert_with(|| Table::new().into());
+/// Parses the key/value pairs of an inline table, on a single line and without a trailing comma.
+#[cfg(not(feature = "toml-1-1"))]
+fn parse_inline_table_entries<'c, 'i>(
+    depth: usize,
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<Vec<(Cow<'i, str>, Value<'i>)>, ContextError> + 'c {
+    move |input| {
+        separated(
+            0..,
+            separated_pair(parse_key, '=', parse_nested_value(depth, comments)),
+            ',',
+        )
+        .parse_next(input)
+    }
+}
 
-            if let Value::Table(ref mut nested_map) = entry {
-                insert_nested_key(nested_map, rest, value);
-            }
+/// Parses the key/value pairs of an inline table, allowing newlines, comments and a trailing
+/// comma, like in arrays.
+#[cfg(feature = "toml-1-1")]
+fn parse_inline_table_entries<'c, 'i>(
+    depth: usize,
+    comments: CommentRecorder<'c, 'i>,
+) -> impl FnMut(&mut &'i str) -> ModalResult<Vec<(Cow<'i, str>, Value<'i>)>, ContextError> + 'c {
+    move |input| {
+        let entry = preceded(
+            parse_whitespace_n_comments(comments),
+            separated_pair(parse_key, '=', parse_nested_value(depth, comments)),
+        );
+        let entries: Vec<_> = separated(0.., entry, ',').parse_next(input)?;
+
+        if !entries.is_empty() {
+            // Ignore trailing comma, if present.
+            opt(',').void().parse_next(input)?;
         }
+
+        parse_whitespace_n_comments(comments).parse_next(input)?;
+
+        Ok(entries)
     }
 }
 
@@ -219,10 +467,8 @@ fn insert_nested_key<'a>(map: &mut Table<'a>, keys: &[Cow<'a, str>], value: Valu
 mod test {
     #[test]
     fn issue_8() {
-        use std::{
-            thread::{sleep, spawn},
-            time::Duration,
-        };
+        use core::time::Duration;
+        use std::thread::{sleep, spawn};
 
         // Reproducer for #8: parsing of a deeply nested array took an **extremely** long time.
         let handle = spawn(|| super::parse("a=[[[[[[[[[[[[[[[[[[[[[[[[[[[").unwrap_err());
@@ -231,4 +477,865 @@ mod test {
             panic!("parsing took way too long.");
         }
     }
+
+    #[test]
+    fn array_of_tables() {
+        use crate::Value;
+
+        let table = super::parse("[[a]]\nx = 1\n\n[[a]]\nx = 2\n").unwrap();
+        let expected = [[("x", Value::from(1))], [("x", Value::from(2))]]
+            .into_iter()
+            .map(|t| t.into_iter().collect::<Value<'_>>())
+            .collect::<Value<'_>>();
+        assert_eq!(table.get("a"), Some(&expected));
+
+        // Only arrays created by `[[a]]` can be appended to.
+        for input in [
+            "a = []\n[[a]]",
+            "a = 1\n[[a]]",
+            "[a]\n[[a]]",
+            "[[a]]\n[a]",
+            "[[a.b]]\n[a]\nb.c = 1",
+            "a = [{ b = 1 }]\n[a.c]",
+        ] {
+            assert!(super::parse(input).is_err(), "{input:?} should fail");
+        }
+    }
+
+    #[test]
+    fn parse_error_kind() {
+        use crate::{Error, ParseErrorKind};
+
+        for (input, kind, expected) in [
+            (
+                "a = \"abc",
+                ParseErrorKind::UnterminatedString,
+                Some("closing quote"),
+            ),
+            ("a = 1_", ParseErrorKind::InvalidNumber, Some("digit")),
+            ("a = 1979-13-27", ParseErrorKind::InvalidDatetime, None),
+            (
+                "a = 1\na = 2",
+                ParseErrorKind::DuplicateKey,
+                Some("a key not already defined"),
+            ),
+            (
+                "a = 1\n[[a]]",
+                ParseErrorKind::InvalidTable,
+                Some("an array of tables or an undefined key"),
+            ),
+            (
+                "a = [1]\na.b = 2",
+                ParseErrorKind::InvalidTable,
+                Some("a table defined by dotted keys"),
+            ),
+            ("a = ?", ParseErrorKind::UnexpectedChar, None),
+            (
+                "[1, 2, 3]",
+                ParseErrorKind::UnexpectedChar,
+                Some("a key or a table header, found an array"),
+            ),
+            (
+                "a = 1\n\"value\"",
+                ParseErrorKind::UnexpectedChar,
+                Some("a key or a table header, found a value"),
+            ),
+            (
+                "a = 1 b = 2",
+                ParseErrorKind::UnexpectedChar,
+                Some("a newline or a comment"),
+            ),
+            (
+                "a = 1\r\n\r",
+                ParseErrorKind::UnexpectedChar,
+                Some("a line feed after a carriage return"),
+            ),
+            (
+                "a = 1\n\0\nb = 2",
+                ParseErrorKind::UnexpectedChar,
+                Some("a key, a table header or a comment, found a control character"),
+            ),
+            (
+                "[a] b = 1",
+                ParseErrorKind::UnexpectedChar,
+                Some("a newline or a comment"),
+            ),
+        ] {
+            let e = match super::parse(input) {
+                Err(Error::Parse(e)) => e,
+                r => panic!("unexpected result for {input:?}: {r:?}"),
+            };
+            assert_eq!(e.kind(), kind, "{input:?}");
+            assert_eq!(e.expected(), expected, "{input:?}");
+            assert_eq!(e.to_string(), e.context.to_string(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn error_predicates() {
+        use crate::{Datetime, Value};
+
+        let e = super::parse("a = ?").unwrap_err();
+        assert!(e.is_parse() && !e.is_deserialize() && !e.is_convert() && !e.is_datetime());
+        assert!(e.as_parse_error().is_some());
+
+        let e = i64::try_from(Value::from("a")).unwrap_err();
+        assert!(e.is_convert() && !e.is_parse());
+        assert!(e.as_parse_error().is_none());
+
+        let e = "nope".parse::<Datetime>().unwrap_err();
+        assert!(e.is_datetime() && !e.is_convert());
+
+        #[cfg(feature = "serde")]
+        {
+            let e = crate::from_str::<i64>("a = 1").unwrap_err();
+            assert!(e.is_deserialize() && !e.is_parse());
+        }
+    }
+
+    #[test]
+    fn empty_tables() {
+        use crate::{Table, Value};
+
+        let empty = || Value::from(Table::new());
+        for input in ["[a]", "[a]\n", "[a] # comment"] {
+            let table = super::parse(input).unwrap();
+            assert_eq!(
+                Value::from(table),
+                [("a", empty())].into_iter().collect::<Value<'_>>()
+            );
+        }
+
+        let nested = [("b", empty())].into_iter().collect::<Value<'_>>();
+        let table = super::parse("[a.b]").unwrap();
+        assert_eq!(table.get("a"), Some(&nested));
+        let table = super::parse("x = 1\n[a.b]\n").unwrap();
+        assert_eq!(table.get("a"), Some(&nested));
+    }
+
+    #[test]
+    fn line_end() {
+        use crate::Value;
+
+        let table = super::parse("a = 1 # c\n[b] # c\nc = 2 # c").unwrap();
+        assert_eq!(table.get("a"), Some(&Value::from(1)));
+        let b = table.get("b").and_then(Value::as_table).unwrap();
+        assert_eq!(b.get("c"), Some(&Value::from(2)));
+
+        for input in [
+            "a = 1 b = 2",
+            "a = 1 [b]",
+            "[a] [b]",
+            "[[a]] b = 1",
+            "a = [] b = 2",
+        ] {
+            super::parse(input).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn explicit_empty_tables() {
+        use crate::{Table, Value};
+
+        let empty = || Value::from(Table::new());
+        // An explicitly declared empty table is kept, not dropped.
+        let table = super::parse("[a]\n").unwrap();
+        assert_eq!(table.get("a"), Some(&empty()));
+        assert!(table.get("b").is_none());
+
+        // Declaring an empty sub-table after its (empty) parent.
+        let table = super::parse("[a]\n[a.b]\n").unwrap();
+        let a = table.get("a").unwrap().as_table().unwrap();
+        assert_eq!(a.get("b"), Some(&empty()));
+
+        // Declaring a deeply nested table, then one of its implicitly created parents.
+        let table = super::parse("[x.y.z.w]\n\n[x]\n").unwrap();
+        let x = table.get("x").unwrap().as_table().unwrap();
+        let y = x.get("y").unwrap().as_table().unwrap();
+        let z = y.get("z").unwrap().as_table().unwrap();
+        assert_eq!(z.get("w"), Some(&empty()));
+        assert_eq!(x.len(), 1);
+    }
+
+    #[test]
+    fn implicit_tables() {
+        use crate::{Table, Value};
+
+        let table = super::parse("[a.b.c]\nx = 1\n[a]\ny = 2\n[a.b]\nz = 3").unwrap();
+        let a = table.get("a").and_then(Value::as_table).unwrap();
+        assert_eq!(a.get("y"), Some(&Value::from(2)));
+        let b = a.get("b").and_then(Value::as_table).unwrap();
+        assert_eq!(b.get("z"), Some(&Value::from(3)));
+        let c = b.get("c").and_then(Value::as_table).unwrap();
+        assert_eq!(c.get("x"), Some(&Value::from(1)));
+
+        let table = super::parse("[a.b.c]").unwrap();
+        let expected = [(
+            "b",
+            [("c", Value::from(Table::new()))]
+                .into_iter()
+                .collect::<Value<'_>>(),
+        )];
+        assert_eq!(table.get("a"), Some(&expected.into_iter().collect()));
+
+        for input in [
+            "[a]\n[a]",
+            "[a]\n[a.b]\n[a]",
+            "[a.b]\n[a]\n[a]",
+            "a = {}\n[a]",
+            // Defining the super-table must not redefine its implicitly created sub-tables.
+            "[a.b.c]\n[a]\nb = 1",
+            "[a.b.c]\n[a]\nb.c = 1",
+            // Inline tables are closed, neither headers nor dotted keys can extend them.
+            "a = {}\n[a.b]",
+            "a = { b = {} }\n[a.b.c]",
+            "a = { x = 1 }\na.y = 2",
+            "[t]\na = { x = 1 }\na.y = 2",
+            // Dotted keys can only extend tables created by dotted keys.
+            "[a.b.c]\nz = 9\n[a]\nb.c.t = 9",
+            "[a.b.c.d]\nz = 9\n[a]\nb.c.d.k.t = 9",
+            "[a.b]\n[a]\nb.x = 1",
+        ] {
+            super::parse(input).unwrap_err();
+        }
+
+        // A super-table defined after an array of tables under it.
+        let table = super::parse("[[a.b]]\nx = 1\n[a]\ny = 2").unwrap();
+        let a = table.get("a").and_then(Value::as_table).unwrap();
+        assert_eq!(a.get("y"), Some(&Value::from(2)));
+        let b = a.get("b").and_then(Value::as_array).unwrap();
+        assert_eq!(b.len(), 1);
+    }
+
+    #[test]
+    fn nested_array_of_tables() {
+        use crate::Value;
+
+        let input = "[[a]]\n[[a.b]]\nx = 1\n[[a.b]]\nx = 2\n[[a]]\nc.d = 3\n[[a.b]]\nx = 4";
+        let table = super::parse(input).unwrap();
+        let a = table.get("a").and_then(Value::as_array).unwrap();
+        assert_eq!(a.len(), 2);
+
+        let xs = |value: &Value<'_>| {
+            let b = value.as_table().and_then(|t| t.get("b")).unwrap();
+            b.as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t.as_table().unwrap().get("x").unwrap().as_i64().unwrap())
+                .collect::<alloc::vec::Vec<_>>()
+        };
+        assert_eq!(xs(a.get(0).unwrap()), [1, 2]);
+        assert_eq!(xs(a.get(1).unwrap()), [4]);
+        let c = a
+            .get(1)
+            .and_then(Value::as_table)
+            .and_then(|t| t.get("c"))
+            .unwrap();
+        assert_eq!(c.as_table().unwrap().get("d"), Some(&Value::from(3)));
+
+        // Sub-table headers land in the last table of the innermost array.
+        let input = "[[a]]\n[[a.b]]\n[a.b.c]\nd = 1\n[[a.b]]\n[a.b.c]\nd = 2";
+        let table = super::parse(input).unwrap();
+        let a = table.get("a").and_then(Value::as_array).unwrap();
+        assert_eq!(a.len(), 1);
+        let b = a
+            .get(0)
+            .and_then(Value::as_table)
+            .and_then(|t| t.get("b"))
+            .and_then(Value::as_array)
+            .unwrap();
+        let ds = b
+            .iter()
+            .map(|t| {
+                let c = t.as_table().unwrap().get("c").unwrap();
+                c.as_table().unwrap().get("d").unwrap().as_i64().unwrap()
+            })
+            .collect::<alloc::vec::Vec<_>>();
+        assert_eq!(ds, [1, 2]);
+
+        // Dotted headers nest the array under implicitly created tables.
+        let table = super::parse("[[servers.http]]\nport = 80\n[[a.b.c]]\n[[a.b.c]]").unwrap();
+        let servers = table.get("servers").and_then(Value::as_table).unwrap();
+        let http = servers.get("http").and_then(Value::as_array).unwrap();
+        assert_eq!(http.len(), 1);
+        let port = http.get(0).and_then(Value::as_table).unwrap().get("port");
+        assert_eq!(port, Some(&Value::from(80)));
+        let c = table
+            .get("a")
+            .and_then(Value::as_table)
+            .and_then(|a| a.get("b"))
+            .and_then(Value::as_table)
+            .and_then(|b| b.get("c"))
+            .and_then(Value::as_array)
+            .unwrap();
+        assert_eq!(c.len(), 2);
+    }
+
+    #[test]
+    fn float_grammar() {
+        use crate::{Error, ParseErrorKind, Value};
+
+        for (input, expected) in [
+            ("+1.0", 1.0),
+            ("-0.0", -0.0),
+            ("1E2", 100.0),
+            ("1e-2", 0.01),
+            ("1_0.0_1", 10.01),
+        ] {
+            let input = format!("f = {input}");
+            let table = super::parse(&input).unwrap();
+            let f = table.get("f").and_then(Value::as_f64).unwrap();
+            assert_eq!(f.to_bits(), f64::to_bits(expected), "{input}");
+        }
+
+        for (input, expected) in [
+            (".7", "a digit before the decimal point"),
+            ("-.7", "a digit before the decimal point"),
+            ("7.", "digit"),
+            ("1e", "digit"),
+            ("1e+", "digit"),
+            ("1.e5", "digit"),
+            ("1_.0", "digit"),
+            ("1._0", "digit"),
+            ("1.0_", "digit"),
+        ] {
+            let input = format!("f = {input}");
+            let e = match super::parse(&input) {
+                Err(Error::Parse(e)) => e,
+                r => panic!("unexpected result for {input:?}: {r:?}"),
+            };
+            assert_eq!(e.kind(), ParseErrorKind::InvalidNumber, "{input}");
+            assert_eq!(e.expected(), Some(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn integer_underscores() {
+        use crate::Value;
+
+        for (input, expected) in [
+            ("1_000_000", 1_000_000),
+            ("-1_000", -1000),
+            ("+1_0", 10),
+            ("-9_223_372_036_854_775_808", i64::MIN),
+            ("0xdead_BEEF", 0xdead_beef),
+            ("0o7_7", 0o77),
+            ("0b1_0", 0b10),
+        ] {
+            let input = format!("i = {input}");
+            let table = super::parse(&input).unwrap();
+            assert_eq!(table.get("i"), Some(&Value::Integer(expected)), "{input}");
+        }
+
+        for input in [
+            "1__0",
+            "1_",
+            "_1",
+            "0x_1",
+            "0x1_",
+            "0b_1",
+            "9_223_372_036_854_775_808",
+        ] {
+            let input = format!("i = {input}");
+            assert!(super::parse(&input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn numbers_and_datetimes() {
+        use crate::{Datetime, Value};
+
+        for (input, expected) in [
+            ("1979", Value::Integer(1979)),
+            ("1_979", Value::Integer(1979)),
+            ("0x7bb", Value::Integer(1979)),
+            ("1979.5", Value::Float(1979.5)),
+            ("1_979e1", Value::Float(19790.0)),
+            ("12", Value::Integer(12)),
+            (
+                "1979-05-27",
+                "1979-05-27".parse::<Datetime>().unwrap().into(),
+            ),
+            ("07:32:00", "07:32:00".parse::<Datetime>().unwrap().into()),
+            (
+                "1979-05-27 07:32:00Z",
+                "1979-05-27T07:32:00Z".parse::<Datetime>().unwrap().into(),
+            ),
+        ] {
+            let input = format!("a = {input}");
+            let table = super::parse(&input).unwrap();
+            assert_eq!(table.get("a"), Some(&expected), "{input}");
+        }
+
+        // Neither a 4-digit year nor a 2-digit hour.
+        for input in ["197-05-27", "19790-05-27", "7:32:00", "1_979-05-27"] {
+            assert!(super::parse(&format!("a = {input}")).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn bom() {
+        let toml = "# Comment.\na = 1\n[b]\nc = \"\u{FEFF}\"\n";
+        let with_bom = format!("\u{FEFF}{toml}");
+        assert_eq!(
+            super::parse(&with_bom).unwrap(),
+            super::parse(toml).unwrap()
+        );
+        assert_eq!(
+            super::parse_table_body("\u{FEFF}a = 1").unwrap(),
+            super::parse_table_body("a = 1").unwrap()
+        );
+
+        // Spans are still relative to the input, BOM included.
+        let spanned = super::parse_spanned(&with_bom).unwrap();
+        assert_eq!(&with_bom[spanned.span(&["a"]).unwrap()], "1");
+        let commented = super::parse_with_comments(&with_bom).unwrap();
+        assert_eq!(commented.comments("a").unwrap().leading(), [" Comment."]);
+
+        // Only a single BOM, at the very start.
+        for invalid in [
+            "\u{FEFF}\u{FEFF}a = 1",
+            " \u{FEFF}a = 1",
+            "a = 1\n\u{FEFF}b = 2",
+            "a = \u{FEFF}1",
+        ] {
+            assert!(super::parse(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn comment_runs_in_arrays() {
+        use crate::Value;
+
+        let toml = "a = [\n  # Ünïcödé.\r\n\t# Another.\n\n  1, # One.\r\n\r\n  # Two.\n  2 ]";
+        let table = super::parse(toml).unwrap();
+        let a = table.get("a").and_then(Value::as_array).unwrap();
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            [&Value::from(1), &Value::from(2)]
+        );
+
+        for invalid in [
+            "a = [ # Bare CR.\r 1 ]",
+            "a = [ \r 1 ]",
+            "a = [ # \u{7f}\n 1 ]",
+        ] {
+            assert!(super::parse(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn secfrac_truncation() {
+        use crate::{Datetime, Value};
+
+        for (fraction, nanosecond) in [
+            ("1", 100_000_000),
+            ("123456789", 123_456_789),
+            ("1234567891234", 123_456_789),
+            // Truncated, not rounded.
+            ("999999999999", 999_999_999),
+            ("0000000009", 0),
+        ] {
+            for text in [
+                alloc::format!("00:00:00.{fraction}"),
+                alloc::format!("1979-05-27T00:00:00.{fraction}Z"),
+            ] {
+                let input = alloc::format!("t = {text}");
+                let parsed = super::parse(&input).unwrap();
+                let parsed = parsed.get("t").and_then(Value::as_datetime).unwrap();
+                let from_str = text.parse::<Datetime>().unwrap();
+                assert_eq!(parsed, from_str, "{text}");
+                assert_eq!(parsed.time.unwrap().nanosecond, nanosecond, "{text}");
+            }
+        }
+    }
+
+    #[test]
+    fn with_comments() {
+        let toml = r#"# Detached, because of the empty line.
+
+# The package.
+[package] # Trailing header comment.
+# The name,
+# on two lines.
+name = "tomling" # Trailing.
+keywords = [
+    # Inside the array.
+    "toml",
+] # After the array.
+"a.b" = 1
+# Comment at the end.
+
+[[bin]]
+# First binary.
+name = "a"
+[[bin]]
+name = "b" # Second binary.
+"#;
+        let commented = super::parse_with_comments(toml).unwrap();
+        assert_eq!(commented.table(), &super::parse(toml).unwrap());
+
+        let comments = |path| {
+            commented
+                .comments(path)
+                .map(|c| (c.leading().to_vec(), c.trailing()))
+        };
+        assert_eq!(
+            comments("package"),
+            Some((vec![" The package."], Some(" Trailing header comment.")))
+        );
+        assert_eq!(
+            comments("package.name"),
+            Some((vec![" The name,", " on two lines."], Some(" Trailing.")))
+        );
+        assert_eq!(
+            comments("package.keywords"),
+            Some((vec![], Some(" After the array.")))
+        );
+        assert_eq!(comments("package.\"a.b\""), None);
+        assert_eq!(
+            comments("bin.name"),
+            Some((vec![" First binary."], Some(" Second binary.")))
+        );
+        assert_eq!(comments("bin"), None);
+        assert_eq!(commented.iter().count(), 4);
+    }
+
+    #[test]
+    fn crlf() {
+        use crate::Value;
+
+        let lf = r#"# A comment.
+[package] # Another one.
+name = "tomling"
+keywords = [
+    "toml", # In an array.
+    "parser",
+]
+inline = { a = 1 }
+
+[[bin]]
+name = "a"
+"#;
+        let crlf = lf.replace('\n', "\r\n");
+        let table = super::parse(&crlf).unwrap();
+        assert_eq!(table, super::parse(lf).unwrap());
+
+        // Only the newline right after the opening delimiter is trimmed.
+        for (input, expected) in [
+            ("a = '''\r\nx\r\ny'''\r\n", "x\r\ny"),
+            ("a = '''\r\n\r\nx'''", "\r\nx"),
+            ("a = '''\n\nx'''", "\nx"),
+            ("a = \"\"\"\r\nx\r\ny\"\"\"\r\n", "x\r\ny"),
+            ("a = \"\"\"\r\n\r\nx\"\"\"", "\r\nx"),
+            // Line ending backslash.
+            ("a = \"\"\"x \\\r\n\r\n  y\"\"\"\r\n", "x y"),
+        ] {
+            let table = super::parse(input).unwrap();
+            assert_eq!(
+                table.get("a").and_then(Value::as_str),
+                Some(expected),
+                "{input:?}"
+            );
+        }
+
+        // A bare carriage return isn't a newline.
+        for input in [
+            "\r",
+            "a = 1\rb = 2",
+            "a = [1,\r2]",
+            "# Comment\r\n\r",
+            "a = \"\"\"x \\\r\n\r  y\"\"\"",
+        ] {
+            assert!(super::parse(input).is_err(), "{input:?} should fail");
+        }
+        assert_eq!("\r\n1\r\n".parse::<Value<'_>>().unwrap(), 1);
+        assert!("1\r".parse::<Value<'_>>().is_err());
+    }
+
+    #[test]
+    fn whitespace() {
+        let table = super::parse("\t[ a . b ]\t\n \tc\t=\t[\t1\t,\t2\t]\t# Tabs.\n").unwrap();
+        assert_eq!(
+            table.get("a").and_then(|a| a.get("b")?.get("c")).unwrap(),
+            &[1, 2]
+        );
+
+        // Only space and tab are whitespace.
+        for ws in ['\u{b}', '\u{c}', '\u{a0}', '\u{3000}'] {
+            for input in [
+                format!("a ={ws}1"),
+                format!("a = 1{ws}"),
+                format!("{ws}a = 1"),
+                format!("a = [1,{ws}2]"),
+                format!("[{ws}a]"),
+            ] {
+                assert!(super::parse(&input).is_err(), "{input:?} should fail");
+            }
+        }
+    }
+
+    #[test]
+    fn table_body() {
+        use crate::{Error, ParseErrorKind};
+
+        let input = r#"
+            # A dependency.
+            version = "1.0"
+            features = ["derive"]
+            git.branch = "main"
+        "#;
+        let table = super::parse_table_body(input).unwrap();
+        assert_eq!(table, super::parse(input).unwrap());
+        assert!(super::parse_table_body("").unwrap().is_empty());
+
+        for input in ["a = 1\n[b]\nc = 2", "[[b]]", "a = 1\na = 2"] {
+            let e = match super::parse_table_body(input) {
+                Err(Error::Parse(e)) => e,
+                r => panic!("unexpected result for {input:?}: {r:?}"),
+            };
+            let (kind, expected) = match input {
+                "a = 1\na = 2" => (ParseErrorKind::DuplicateKey, "a key not already defined"),
+                _ => (ParseErrorKind::InvalidTable, "a key, not a table header"),
+            };
+            assert_eq!(e.kind(), kind, "{input:?}");
+            assert_eq!(e.expected(), Some(expected), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn multibyte() {
+        use crate::Value;
+
+        for input in [
+            "ключ = 1",
+            "é = 1",
+            "[ключ]",
+            "a.ключ = 1",
+            "a = { ключ = 1 }",
+        ] {
+            super::parse(input).unwrap_err();
+        }
+
+        let content = "§ тест 𝓼𝓽𝓻 €";
+        for quote in ["\"", "'", "\"\"\"", "'''"] {
+            let input = alloc::format!("\"ключ\" = {quote}{content}{quote}");
+            let table = super::parse(&input).unwrap();
+            let value = table.get("ключ").and_then(Value::as_str).unwrap();
+            assert_eq!(value.as_bytes(), content.as_bytes(), "{input}");
+        }
+    }
+
+    #[test]
+    fn no_panic() {
+        use alloc::{format, string::String, vec::Vec};
+
+        let nested = |open: &str, close: &str, depth: usize| {
+            format!("a = {}1{}", open.repeat(depth), close.repeat(depth))
+        };
+        let mut inputs: Vec<String> = [
+            "[]",
+            "[[]]",
+            "[.]",
+            "[a.]",
+            "a. = 1",
+            ".a = 1",
+            "=",
+            "a =",
+            "a = 9223372036854775808",
+            "a = -9223372036854775809",
+            "a = 0x8000000000000000",
+            "a = 1e400",
+            "a = 1979-05-27T07:32:00.99999999999999999999999Z",
+            "a = 1979-05-27T07:32:00+99:99",
+            "a = 1979-13-32",
+            "a = 99:99:99",
+            "a = \"\\",
+            "a = '''",
+            "a = { b = ",
+            "a = [ # comment",
+        ]
+        .into_iter()
+        .map(Into::into)
+        .collect();
+        for depth in [super::MAX_NESTING, super::MAX_NESTING + 1, 100_000] {
+            inputs.push(nested("[", "]", depth));
+            inputs.push(nested("{ b = ", " }", depth));
+            inputs.push(nested("[{ b = ", " }]", depth / 2));
+        }
+
+        // Run with a small stack, to catch unbounded recursion.
+        let handle = std::thread::Builder::new()
+            .stack_size(4 * 1024 * 1024)
+            .spawn(move || {
+                for input in &inputs {
+                    let _ = super::parse(input);
+                }
+            })
+            .unwrap();
+        handle.join().unwrap();
+
+        super::parse(&nested("[", "]", super::MAX_NESTING)).unwrap();
+        super::parse(&nested("[", "]", super::MAX_NESTING + 1)).unwrap_err();
+    }
+
+    #[test]
+    fn comments() {
+        let input = r##"# Leading.
+a = "# not a comment" # Trailing.
+b = '''
+# not a comment either
+''' #multiline
+c = """a \""" # still a string"""""
+d = [ # In an array.
+    1, # After an element.
+]
+e = { f = '#' }
+g = [[1, # Nested.
+    2], [3]] # After a nested array.
+[h] # Header.
+# Last, without a newline."##;
+        let comments = super::parse_comments(input).unwrap();
+        let texts = comments.iter().map(|c| c.text()).collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            [
+                " Leading.",
+                " Trailing.",
+                "multiline",
+                " In an array.",
+                " After an element.",
+                " Nested.",
+                " After a nested array.",
+                " Header.",
+                " Last, without a newline.",
+            ]
+        );
+        for comment in &comments {
+            assert_eq!(input[comment.span()], alloc::format!("#{}", comment.text()));
+        }
+
+        let comments = super::parse_comments("a = 1 # CRLF\r\n").unwrap();
+        assert_eq!(comments[0].text(), " CRLF");
+        assert_eq!(comments[0].span(), 6..12);
+
+        super::parse_comments("a = # nope").unwrap_err();
+    }
+
+    #[test]
+    fn spanned() {
+        let input = r#"title = "spans"
+[server]
+host = { name = "localhost" }   # A comment.
+ports = [80, 443]
+tls.enabled = true
+[[server.backup]]
+host = 'a'
+[[server.backup]]
+host = 'b'
+"#;
+        let spanned = super::parse_spanned(input).unwrap();
+        assert_eq!(spanned.table(), &super::parse(input).unwrap());
+        let text = |path: &[&str]| &input[spanned.span(path).unwrap()];
+        assert_eq!(text(&["title"]), r#""spans""#);
+        assert_eq!(text(&["server"]), "[server]");
+        assert_eq!(text(&["server", "host"]), r#"{ name = "localhost" }"#);
+        assert_eq!(text(&["server", "ports"]), "[80, 443]");
+        assert_eq!(text(&["server", "tls", "enabled"]), "true");
+        assert!(spanned.span(&["server", "tls"]).is_none());
+        assert!(spanned.span(&["server", "host", "name"]).is_none());
+
+        let backups = spanned
+            .spans(&["server", "backup"])
+            .map(|span| &input[span])
+            .collect::<Vec<_>>();
+        assert_eq!(backups, ["[[server.backup]]", "[[server.backup]]"]);
+        let hosts = spanned
+            .spans(&["server", "backup", "host"])
+            .map(|span| &input[span])
+            .collect::<Vec<_>>();
+        assert_eq!(hosts, ["'a'", "'b'"]);
+
+        assert_eq!(spanned.into_table(), super::parse(input).unwrap());
+        super::parse_spanned("a = 1\na = 2").unwrap_err();
+    }
+
+    #[test]
+    fn escapes() {
+        use crate::{Error, ParseErrorKind, Value};
+        use alloc::borrow::Cow;
+
+        let table = super::parse(
+            r#"a = "tab\there \"quoted\" \\ é\U0001F600"
+b = """
+line 1 \
+    line 2\n""\""""
+c = "no escapes""#,
+        )
+        .unwrap();
+        assert_eq!(
+            table.get("a"),
+            Some(&Value::from("tab\there \"quoted\" \\ \u{e9}\u{1F600}"))
+        );
+        assert_eq!(table.get("b"), Some(&Value::from("line 1 line 2\n\"\"\"")));
+        // Strings without escapes are borrowed from the input.
+        assert!(matches!(
+            table.get("c"),
+            Some(Value::String(Cow::Borrowed("no escapes")))
+        ));
+
+        let toml_1_1 = super::parse(r#"a = "\e[0m\x41""#);
+        if cfg!(feature = "toml-1-1") {
+            let table = toml_1_1.unwrap();
+            assert_eq!(table.get("a"), Some(&Value::from("\u{1b}[0mA")));
+        } else {
+            toml_1_1.unwrap_err();
+        }
+
+        for input in [
+            r#"a = "\q""#,
+            r#"a = "\u00""#,
+            r#"a = "\uD800""#,
+            r#"a = "\U00110000""#,
+            r#"a = "\u+0FF""#,
+            r#"a = "\xAg""#,
+            "a = \"\"\"\\ x\"\"\"",
+        ] {
+            match super::parse(input) {
+                Err(Error::Parse(e)) => assert_eq!(e.kind(), ParseErrorKind::InvalidEscape),
+                r => panic!("unexpected result for {input:?}: {r:?}"),
+            }
+        }
+        super::parse("a = \"no\nnewlines\"").unwrap_err();
+    }
+
+    #[test]
+    fn multiline_inline_tables() {
+        use crate::Value;
+
+        let inputs = [
+            "a = { b = 1,\n  c = 2 }",
+            "a = {\n  b = 1, # comment\n  c = 2,\n}",
+            "a = { b = 1, c = 2, }",
+        ];
+        for input in inputs {
+            let result = super::parse(input);
+            if cfg!(feature = "toml-1-1") {
+                let table = result.unwrap();
+                let a = table.get("a").and_then(Value::as_table).unwrap();
+                assert_eq!(a.get("b"), Some(&Value::from(1)));
+                assert_eq!(a.get("c"), Some(&Value::from(2)));
+            } else {
+                result.unwrap_err();
+            }
+        }
+
+        // Still valid TOML 1.0.
+        let table = super::parse("a = { b = 1, c = 2 }").unwrap();
+        assert_eq!(table.get("a").and_then(Value::as_table).unwrap().len(), 2);
+        // Never valid.
+        for input in ["a = { , }", "a = { b = 1,, }", "a = { b = 1 c = 2 }"] {
+            super::parse(input).unwrap_err();
+        }
+    }
 }
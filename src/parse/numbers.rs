@@ -1,7 +1,8 @@
+use alloc::borrow::Cow;
 use core::ops::RangeInclusive;
 
 use winnow::{
-    combinator::{alt, cut_err, opt, peek, preceded, repeat, trace},
+    combinator::{alt, cut_err, fail, opt, peek, preceded, repeat, trace},
     dispatch,
     error::{StrContext, StrContextValue},
     token::{one_of, rest, take},
@@ -31,11 +32,11 @@ const FALSE: &str = "false";
 pub(crate) fn integer(input: &mut &str) -> ModalResult<i64> {
     trace("integer",
     dispatch! {peek(opt::<_, &str, _, _>(take(2usize)));
-        Some("0x") => cut_err(hex_int.try_map(|s| i64::from_str_radix(&s.replace('_', ""), 16))),
-        Some("0o") => cut_err(oct_int.try_map(|s| i64::from_str_radix(&s.replace('_', ""), 8))),
-        Some("0b") => cut_err(bin_int.try_map(|s| i64::from_str_radix(&s.replace('_', ""), 2))),
+        Some("0x") => cut_err(hex_int.try_map(|s| i64::from_str_radix(&strip_underscores(s), 16))),
+        Some("0o") => cut_err(oct_int.try_map(|s| i64::from_str_radix(&strip_underscores(s), 8))),
+        Some("0b") => cut_err(bin_int.try_map(|s| i64::from_str_radix(&strip_underscores(s), 2))),
         _ => dec_int.and_then(cut_err(rest
-            .try_map(|s: &str| s.replace('_', "").parse())))
+            .try_map(|s: &str| strip_underscores(s).parse())))
     })
     .parse_next(input)
 }
@@ -174,6 +175,15 @@ fn bin_int<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
 const BIN_PREFIX: &str = "0b";
 const DIGIT0_1: RangeInclusive<u8> = b'0'..=b'1';
 
+/// Remove the underscores separating the digits of a number, only allocating if there are any.
+fn strip_underscores(s: &str) -> Cow<'_, str> {
+    if s.contains('_') {
+        Cow::Owned(s.replace('_', ""))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
 // ;; Float
 
 // float = float-int-part ( exp / frac [ exp ] )
@@ -184,10 +194,11 @@ pub(crate) fn float(input: &mut &str) -> ModalResult<f64> {
         "float",
         alt((
             float_.and_then(cut_err(
-                rest.try_map(|s: &str| s.replace('_', "").parse())
+                rest.try_map(|s: &str| strip_underscores(s).parse())
                     .verify(|f: &f64| *f != f64::INFINITY),
             )),
             special_float,
+            leading_point,
         ))
         .context(StrContext::Label("floating-point number")),
     )
@@ -203,22 +214,27 @@ fn float_<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
         .parse_next(input)
 }
 
+// The integer part can't be omitted, e.g `.7` or `-.7`.
+fn leading_point(input: &mut &str) -> ModalResult<f64> {
+    preceded(
+        (opt(one_of((b'+', b'-'))), peek('.')),
+        cut_err(fail).context(StrContext::Expected(StrContextValue::Description(
+            "a digit before the decimal point",
+        ))),
+    )
+    .parse_next(input)
+}
+
 // frac = decimal-point zero-prefixable-int
 // decimal-point = %x2E               ; .
 fn frac<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
-    (
-        '.',
-        cut_err(zero_prefixable_int)
-            .context(StrContext::Expected(StrContextValue::Description("digit"))),
-    )
-        .take()
-        .parse_next(input)
+    ('.', cut_err(zero_prefixable_int)).take().parse_next(input)
 }
 
 // zero-prefixable-int = DIGIT *( DIGIT / underscore DIGIT )
 fn zero_prefixable_int<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
     (
-        digit,
+        digit.context(StrContext::Expected(StrContextValue::Description("digit"))),
         repeat(
             0..,
             alt((
@@ -251,12 +267,9 @@ fn exp<'i>(input: &mut &'i str) -> ModalResult<&'i str> {
 
 // special-float = [ minus / plus ] ( inf / nan )
 fn special_float(input: &mut &str) -> ModalResult<f64> {
+    // The sign of `nan` is dropped, so all NaNs are the same.
     (opt(one_of((b'+', b'-'))), alt((inf, nan)))
-        .map(|(s, f)| match s {
-            Some('+') | None => f,
-            Some('-') => -f,
-            _ => unreachable!("one_of should prevent this"),
-        })
+        .map(|(s, f)| if s == Some('-') && !f.is_nan() { -f } else { f })
         .parse_next(input)
 }
 // inf = %x69.6e.66  ; inf
@@ -1,64 +1,202 @@
 use crate::Value;
 
+use alloc::{borrow::Cow, string::String};
 use winnow::{
-    combinator::{alt, delimited},
-    error::ContextError,
-    token::take_until,
+    combinator::{alt, cut_err, delimited, fail, opt, preceded},
+    error::{ContextError, StrContext, StrContextValue},
+    stream::Stream as _,
+    token::{any, take, take_till, take_until},
     ModalResult, Parser,
 };
 
+use super::ignored::{newline, parse_whitespace_n_newlines, ws0};
+
 /// Parses a string value enclosed in quotes
 pub(crate) fn parse<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
-    // TODO:
-    // * Handle escape sequences.
     alt((
         parse_multiline_basic,
         parse_basic,
         parse_multiline_literal,
         parse_literal,
     ))
+    .map(Value::String)
     .parse_next(input)
 }
 
 /// Parses a basic string value enclosed in quotes.
-pub(crate) fn parse_basic<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
-    delimited('"', take_until(0.., '"'), '"')
-        .map(Into::into)
+pub(crate) fn parse_basic<'i>(input: &mut &'i str) -> ModalResult<Cow<'i, str>, ContextError> {
+    delimited('"', cut_err(basic_content(false)), '"')
+        .context(StrContext::Label("string"))
+        .context(StrContext::Expected(CLOSING_QUOTE))
         .parse_next(input)
 }
 
 /// Parses a literal string value enclosed in single quotes.
-pub(crate) fn parse_literal<'i>(input: &mut &'i str) -> ModalResult<Value<'i>, ContextError> {
-    delimited('\'', take_until(0.., '\''), '\'')
+pub(crate) fn parse_literal<'i>(input: &mut &'i str) -> ModalResult<Cow<'i, str>, ContextError> {
+    delimited('\'', cut_err(take_until(0.., '\'')), '\'')
         .map(Into::into)
+        .context(StrContext::Label("string"))
+        .context(StrContext::Expected(CLOSING_QUOTE))
         .parse_next(input)
 }
 
 /// Parses a multiline basic string value enclosed in triple quotes.
 pub(crate) fn parse_multiline_basic<'i>(
     input: &mut &'i str,
-) -> ModalResult<Value<'i>, ContextError> {
+) -> ModalResult<Cow<'i, str>, ContextError> {
     delimited(
-        "\"\"\"",
-        take_until(0.., "\"\"\"").map(|s: &str| {
-            // Trim leading newlines.
-            s.trim_start_matches('\n')
-        }),
+        // The newline right after the opening delimiter is trimmed.
+        ("\"\"\"", opt(newline)),
+        cut_err(basic_content(true)),
         "\"\"\"",
     )
-    .map(Into::into)
+    .context(StrContext::Label("string"))
+    .context(StrContext::Expected(CLOSING_QUOTE))
     .parse_next(input)
 }
 
 /// Parses a literal multiline string value enclosed in triple single quotes (`'''`).
 pub(crate) fn parse_multiline_literal<'i>(
     input: &mut &'i str,
-) -> ModalResult<Value<'i>, ContextError> {
+) -> ModalResult<Cow<'i, str>, ContextError> {
     delimited(
-        "'''",
-        take_until(0.., "'''").map(|s: &str| s.trim_start_matches('\n')), // Trim leading newlines
+        // The newline right after the opening delimiter is trimmed.
+        ("'''", opt(newline)),
+        cut_err(take_until(0.., "'''")),
         "'''",
     )
     .map(Into::into)
+    .context(StrContext::Label("string"))
+    .context(StrContext::Expected(CLOSING_QUOTE))
     .parse_next(input)
 }
+
+/// Parses the content of a basic string, up to its closing delimiter, resolving escapes.
+///
+/// The content is only copied if it contains escape sequences.
+fn basic_content<'i>(
+    multiline: bool,
+) -> impl FnMut(&mut &'i str) -> ModalResult<Cow<'i, str>, ContextError> {
+    move |input| {
+        let start = *input;
+        // Only allocated on the first escape sequence.
+        let mut unescaped: Option<String> = None;
+        loop {
+            let chunk = take_till(0.., |c| {
+                c == '"' || c == '\\' || (!multiline && matches!(c, '\n' | '\r'))
+            })
+            .parse_next(input)?;
+            if let Some(unescaped) = &mut unescaped {
+                unescaped.push_str(chunk);
+            }
+
+            match input.chars().next() {
+                Some('"') if !multiline => break,
+                Some('"') => {
+                    let quotes = input.len() - input.trim_start_matches('"').len();
+                    if quotes >= 3 {
+                        // Up to 2 quotes right before the closing delimiter are part of the
+                        // content.
+                        let quotes = take((quotes - 3).min(2)).parse_next(input)?;
+                        if let Some(unescaped) = &mut unescaped {
+                            unescaped.push_str(quotes);
+                        }
+                        break;
+                    }
+                    let quotes = take(quotes).parse_next(input)?;
+                    if let Some(unescaped) = &mut unescaped {
+                        unescaped.push_str(quotes);
+                    }
+                }
+                Some('\\') => {
+                    let unescaped = unescaped
+                        .get_or_insert_with(|| String::from(&start[..start.len() - input.len()]));
+                    preceded('\\', escape(multiline, unescaped)).parse_next(input)?;
+                }
+                // Newlines in single-line strings or EOF.
+                _ => return fail.parse_next(input),
+            }
+        }
+
+        Ok(match unescaped {
+            Some(unescaped) => Cow::Owned(unescaped),
+            None => Cow::Borrowed(&start[..start.len() - input.len()]),
+        })
+    }
+}
+
+/// Parses an escape sequence (after the `\`), pushing the escaped character to `unescaped`.
+fn escape<'u>(
+    multiline: bool,
+    unescaped: &'u mut String,
+) -> impl FnMut(&mut &str) -> ModalResult<(), ContextError> + 'u {
+    move |input| {
+        let start = input.checkpoint();
+        let c = match any.parse_next(input)? {
+            'b' => '\u{8}',
+            't' => '\t',
+            'n' => '\n',
+            'f' => '\u{c}',
+            'r' => '\r',
+            '"' => '"',
+            '\\' => '\\',
+            'u' => unicode(4).parse_next(input)?,
+            'U' => unicode(8).parse_next(input)?,
+            #[cfg(feature = "toml-1-1")]
+            'e' => '\u{1b}',
+            #[cfg(feature = "toml-1-1")]
+            'x' => unicode(2).parse_next(input)?,
+            // A line ending backslash trims all whitespace up to the next non-whitespace.
+            ' ' | '\t' | '\n' | '\r' if multiline => {
+                input.reset(&start);
+                return (ws0, cut_err(newline), parse_whitespace_n_newlines)
+                    .void()
+                    .context(StrContext::Label("escape sequence"))
+                    .context(StrContext::Expected(StrContextValue::Description(
+                        "a newline after a line ending backslash",
+                    )))
+                    .parse_next(input);
+            }
+            _ => {
+                input.reset(&start);
+                return cut_err(fail)
+                    .context(StrContext::Label("escape sequence"))
+                    .context(StrContext::Expected(ESCAPE))
+                    .parse_next(input);
+            }
+        };
+        unescaped.push(c);
+
+        Ok(())
+    }
+}
+
+/// Parses the `digits` hexadecimal digits of a unicode escape sequence.
+fn unicode(digits: usize) -> impl FnMut(&mut &str) -> ModalResult<char, ContextError> {
+    move |input| {
+        cut_err(
+            take(digits)
+                .verify_map(|hex: &str| {
+                    // `from_str_radix` also accepts a leading `+`.
+                    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                        return None;
+                    }
+                    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                })
+                .context(StrContext::Label("escape sequence"))
+                .context(StrContext::Expected(StrContextValue::Description(
+                    "a unicode scalar value",
+                ))),
+        )
+        .parse_next(input)
+    }
+}
+
+const CLOSING_QUOTE: StrContextValue = StrContextValue::Description("closing quote");
+#[cfg(not(feature = "toml-1-1"))]
+const ESCAPE: StrContextValue =
+    StrContextValue::Description("one of `b`, `t`, `n`, `f`, `r`, `\"`, `\\`, `u` or `U`");
+#[cfg(feature = "toml-1-1")]
+const ESCAPE: StrContextValue = StrContextValue::Description(
+    "one of `b`, `t`, `n`, `f`, `r`, `e`, `\"`, `\\`, `x`, `u` or `U`",
+);
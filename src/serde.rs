@@ -1,4 +1,4 @@
-use alloc::borrow::Cow;
+use alloc::{borrow::Cow, vec::Vec};
 
 use crate::{
     array::{self, Array},
@@ -19,13 +19,30 @@ where
 {
     let value = crate::parse(s)?;
 
+    from_table(value)
+}
+
+/// Deserialize a type from a parsed TOML table.
+pub(crate) fn from_table<'de, T>(table: Table<'de>) -> Result<T, Error>
+where
+    T: de::Deserialize<'de>,
+{
     T::deserialize(ValueDeserializer {
-        value: Some(Value::Table(value)),
+        value: Some(Value::Table(table)),
         date: None,
         time: None,
     })
 }
 
+/// The name `Datetime` is (de)serialized with.
+pub(crate) const DATETIME_NAME: &str = "Datetime";
+
+/// The key that native datetimes are tagged with, when (de)serialized as a map.
+///
+/// The derived `Datetime` deserializer ignores it, while the `Value` one uses it to tell datetimes
+/// and tables apart. A TOML serializer would use it to emit bare datetimes.
+pub(crate) const DATETIME_MARKER: &str = "$__tomling_private_datetime";
+
 #[derive(Debug)]
 struct ValueDeserializer<'de> {
     value: Option<Value<'de>>,
@@ -68,6 +85,64 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         }
     }
 
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Value::String(s)) => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => visitor.visit_char(c),
+                    _ => Err(de::Error::invalid_value(
+                        de::Unexpected::Str(&s),
+                        &"single character",
+                    )),
+                }
+            }
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::Other("non-string"),
+                &visitor,
+            )),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Value::String(Cow::Borrowed(s))) => visitor.visit_borrowed_bytes(s.as_bytes()),
+            Some(Value::String(Cow::Owned(s))) => visitor.visit_byte_buf(s.into_bytes()),
+            Some(Value::Array(array)) => {
+                let bytes = array
+                    .iter()
+                    .map(|value| match value {
+                        Value::Integer(i) => u8::try_from(*i).map_err(|_| {
+                            de::Error::invalid_value(de::Unexpected::Signed(*i), &"a byte")
+                        }),
+                        _ => Err(de::Error::invalid_type(
+                            de::Unexpected::Other("non-integer"),
+                            &"a byte",
+                        )),
+                    })
+                    .collect::<Result<Vec<u8>, Error>>()?;
+                visitor.visit_byte_buf(bytes)
+            }
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::Other("non-string and non-array"),
+                &visitor,
+            )),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
     fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
@@ -175,7 +250,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
 
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
@@ -192,25 +267,37 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
                     visitor.visit_map(DatetimeDeserializer::new(dt))
                 }
             }
+            // Quoted datetimes, as often produced by other tools.
+            Some(Value::String(s)) if name == DATETIME_NAME => {
+                let dt = s.parse::<Datetime>().map_err(|_| {
+                    <Error as de::Error>::invalid_value(
+                        de::Unexpected::Str(&s),
+                        &"a TOML date-time",
+                    )
+                })?;
+                visitor.visit_map(DatetimeDeserializer::new(dt))
+            }
             _ => self.deserialize_any(visitor),
         }
     }
 
     serde::forward_to_deserialize_any! {
         i8 i16 i32 i128 u8 u16 u32 u64 u128 f32
-        char string bytes byte_buf unit unit_struct
+        string unit unit_struct
         tuple tuple_struct identifier ignored_any
     }
 }
 
 struct SeqDeserializer<'de> {
     iter: array::IntoIter<'de>,
+    index: usize,
 }
 
 impl<'de> SeqDeserializer<'de> {
     fn new(array: Array<'de>) -> Self {
         SeqDeserializer {
             iter: array.into_iter(),
+            index: 0,
         }
     }
 }
@@ -228,13 +315,19 @@ impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
                 date: None,
                 time: None,
             };
-            seed.deserialize(de).map(Some)
+            let index = self.index;
+            self.index += 1;
+            seed.deserialize(de)
+                .map(Some)
+                .map_err(|e| e.with_index(index))
         })
     }
 }
 
 struct MapDeserializer<'de> {
     iter: table::IntoIter<'de>,
+    // The key of `value`, for the path in errors.
+    key: Option<Cow<'de, str>>,
     value: Option<Value<'de>>,
 }
 
@@ -242,6 +335,7 @@ impl<'de> MapDeserializer<'de> {
     fn new(table: Table<'de>) -> Self {
         MapDeserializer {
             iter: table.into_iter(),
+            key: None,
             value: None,
         }
     }
@@ -256,13 +350,13 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
     {
         if let Some((key, value)) = self.iter.next() {
             self.value = Some(value);
+            let key = &*self.key.insert(key);
             match key {
-                Cow::Owned(s) => seed.deserialize(StrDeserializer::new(&s).into_deserializer()),
-                Cow::Borrowed(s) => {
-                    seed.deserialize(BorrowedStrDeserializer::new(s).into_deserializer())
-                }
+                Cow::Owned(s) => seed.deserialize(StrDeserializer::<Error>::new(s)),
+                Cow::Borrowed(s) => seed.deserialize(BorrowedStrDeserializer::new(s)),
             }
             .map(Some)
+            .map_err(|e| e.with_key(key))
         } else {
             Ok(None)
         }
@@ -273,11 +367,16 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
         V: DeserializeSeed<'de>,
     {
         match self.value.take() {
-            Some(value) => seed.deserialize(ValueDeserializer {
-                value: Some(value),
-                date: None,
-                time: None,
-            }),
+            Some(value) => seed
+                .deserialize(ValueDeserializer {
+                    value: Some(value),
+                    date: None,
+                    time: None,
+                })
+                .map_err(|e| match &self.key {
+                    Some(key) => e.with_key(key),
+                    None => e,
+                }),
             None => Err(de::Error::custom("value is missing")),
         }
     }
@@ -293,13 +392,14 @@ impl DatetimeDeserializer {
     fn new(dt: Datetime) -> Self {
         DatetimeDeserializer {
             dt,
-            stage: DatetimeDeserializerStage::Date,
+            stage: DatetimeDeserializerStage::Marker,
         }
     }
 }
 
 #[derive(Debug, PartialEq)]
 enum DatetimeDeserializerStage {
+    Marker,
     Date,
     Time,
     Offset,
@@ -315,6 +415,7 @@ impl<'de> MapAccess<'de> for DatetimeDeserializer {
         K: DeserializeSeed<'de>,
     {
         let de = match self.stage {
+            DatetimeDeserializerStage::Marker => DATETIME_MARKER.into_deserializer(),
             DatetimeDeserializerStage::Date => "date".into_deserializer(),
             DatetimeDeserializerStage::Time => "time".into_deserializer(),
             DatetimeDeserializerStage::Offset => "offset".into_deserializer(),
@@ -328,6 +429,14 @@ impl<'de> MapAccess<'de> for DatetimeDeserializer {
         V: DeserializeSeed<'de>,
     {
         let de = match self.stage {
+            DatetimeDeserializerStage::Marker => {
+                self.stage = DatetimeDeserializerStage::Date;
+                ValueDeserializer {
+                    value: Some(Value::Boolean(true)),
+                    date: None,
+                    time: None,
+                }
+            }
             DatetimeDeserializerStage::Date => {
                 self.stage = DatetimeDeserializerStage::Time;
                 match self.dt.date {
@@ -513,3 +622,82 @@ impl<'de> MapAccess<'de> for TimeDeserializer {
         seed.deserialize(I64Deserializer::new(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    #[test]
+    fn deserialize_char() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Config {
+            sep: char,
+        }
+
+        let config: Config = crate::from_str(r#"sep = ",""#).unwrap();
+        assert_eq!(config.sep, ',');
+        let config: Config = crate::from_str(r#"sep = "§""#).unwrap();
+        assert_eq!(config.sep, '§');
+
+        for toml in [r#"sep = ",;""#, r#"sep = """#] {
+            let e = crate::from_str::<Config>(toml).unwrap_err();
+            assert!(e.to_string().contains("expected single character"), "{e}");
+        }
+        crate::from_str::<Config>("sep = 1").unwrap_err();
+    }
+
+    #[test]
+    fn deserialize_bytes() {
+        use alloc::vec::Vec;
+        use core::fmt;
+        use serde::de::{Deserialize, Deserializer, Error, Visitor};
+
+        // What `serde_bytes::ByteBuf` does.
+        #[derive(Debug)]
+        struct ByteBuf(Vec<u8>);
+
+        impl<'de> Deserialize<'de> for ByteBuf {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct BytesVisitor;
+
+                impl<'de> Visitor<'de> for BytesVisitor {
+                    type Value = ByteBuf;
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str("bytes")
+                    }
+
+                    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+                        Ok(ByteBuf(v.to_vec()))
+                    }
+
+                    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+                        Ok(ByteBuf(v))
+                    }
+                }
+
+                deserializer.deserialize_byte_buf(BytesVisitor)
+            }
+        }
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Data {
+            array: ByteBuf,
+            string: ByteBuf,
+        }
+
+        let data: Data = crate::from_str("array = [0, 1, 255]\nstring = \"aé\"").unwrap();
+        assert_eq!(data.array.0, [0, 1, 255]);
+        assert_eq!(data.string.0, "aé".as_bytes());
+
+        for toml in [
+            "array = [256]",
+            "array = [-1]",
+            "array = [\"a\"]",
+            "array = 1",
+        ] {
+            let toml = alloc::format!("{toml}\nstring = \"\"");
+            crate::from_str::<Data>(&toml).unwrap_err();
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! A TOML table, along with the spans of its values.
+
+use alloc::{borrow::Cow, vec::Vec};
+use core::ops::Range;
+
+use crate::Table;
+
+/// A parsed TOML document, along with where its values and tables are defined in the input.
+///
+/// Created by [`crate::parse_spanned`]. Spans are recorded for the values of key/value pairs
+/// (e.g `"tomling"` in `name = "tomling"`) and for table headers (e.g `[package]`), keyed by their
+/// full path. The keys and values nested in inline tables and arrays don't have spans of their
+/// own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedTable<'i> {
+    table: Table<'i>,
+    spans: Spans<'i>,
+}
+
+/// The spans of a document, with the path of what's at each span, in document order.
+pub(crate) type Spans<'i> = Vec<(Vec<Cow<'i, str>>, Range<usize>)>;
+
+impl<'i> SpannedTable<'i> {
+    pub(crate) fn new(table: Table<'i>, spans: Spans<'i>) -> Self {
+        Self { table, spans }
+    }
+
+    pub(crate) fn into_parts(self) -> (Table<'i>, Spans<'i>) {
+        (self.table, self.spans)
+    }
+
+    /// The parsed table.
+    pub fn table(&self) -> &Table<'i> {
+        &self.table
+    }
+
+    /// Convert into the parsed table, dropping the spans.
+    pub fn into_table(self) -> Table<'i> {
+        self.table
+    }
+
+    /// The span of the value or table header at `path`.
+    ///
+    /// For arrays of tables, this is the span of the first element. Use [`SpannedTable::spans`]
+    /// to get the spans of all the elements.
+    pub fn span(&self, path: &[&str]) -> Option<Range<usize>> {
+        self.spans(path).next()
+    }
+
+    /// The spans of the values or table headers at `path`, in document order.
+    ///
+    /// There's more than one span only for arrays of tables (one per `[[header]]`), and for keys
+    /// in arrays of tables (one per element defining the key).
+    pub fn spans<'s>(&'s self, path: &'s [&str]) -> impl Iterator<Item = Range<usize>> + 's {
+        self.spans
+            .iter()
+            .filter(move |(p, _)| p.iter().map(|k| &**k).eq(path.iter().copied()))
+            .map(|(_, span)| span.clone())
+    }
+}
@@ -1,24 +1,44 @@
 //! A TOML table.
 
-use crate::Value;
-use alloc::{borrow::Cow, collections::BTreeMap};
+use crate::{Error, Value};
+use alloc::{borrow::Cow, vec::Vec};
+use core::str::FromStr;
+
+#[cfg(not(feature = "hashbrown"))]
+use alloc::collections::{btree_map as map, BTreeMap as Map};
+#[cfg(feature = "hashbrown")]
+use hashbrown::{hash_map as map, HashMap as Map};
+
+/// The entry type of the map backing [`Table`].
+#[cfg(not(feature = "hashbrown"))]
+type MapEntry<'t, 'a> = map::Entry<'t, Cow<'a, str>, Value<'a>>;
+#[cfg(feature = "hashbrown")]
+type MapEntry<'t, 'a> = map::Entry<'t, Cow<'a, str>, Value<'a>, hashbrown::DefaultHashBuilder>;
 
 /// A TOML table.
+///
+/// When parsed, keys borrow from the input, unless they contain escape sequences (e.g
+/// `"caf\u00e9"`), which need to be unescaped into a new string.
+///
+/// Iteration is sorted by key, unless the `hashbrown` feature is enabled. Then the table is backed
+/// by a hash map, for faster lookups, and the iteration order is unspecified.
 #[derive(Debug, Default, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
-pub struct Table<'a>(
-    #[cfg_attr(feature = "serde", serde(borrow))] BTreeMap<Cow<'a, str>, Value<'a>>,
-);
+pub struct Table<'a>(#[cfg_attr(feature = "serde", serde(borrow))] Map<Cow<'a, str>, Value<'a>>);
 
 impl<'a> Table<'a> {
     /// Create a new table.
     pub fn new() -> Self {
-        Self(BTreeMap::new())
+        Self(Map::new())
     }
 
-    /// Insert a key-value pair into the table.
-    pub fn insert(&mut self, key: Cow<'a, str>, value: Value<'a>) {
+    /// Insert a key-value pair into the table, replacing any existing value for the key.
+    ///
+    /// Returns the table so calls can be chained.
+    pub fn insert(&mut self, key: Cow<'a, str>, value: Value<'a>) -> &mut Self {
         self.0.insert(key, value);
+
+        self
     }
 
     /// Get the value for the given key.
@@ -26,6 +46,16 @@ impl<'a> Table<'a> {
         self.0.get(key)
     }
 
+    /// Get the value for the given key mutably.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value<'a>> {
+        self.0.get_mut(key)
+    }
+
+    /// Remove the value for the given key, returning it.
+    pub fn remove(&mut self, key: &str) -> Option<Value<'a>> {
+        self.0.remove(key)
+    }
+
     /// Get the length of the table.
     pub fn len(&self) -> usize {
         self.0.len()
@@ -41,15 +71,224 @@ impl<'a> Table<'a> {
         Iter::new(self)
     }
 
-    pub(crate) fn entry(
-        &mut self,
-        key: Cow<'a, str>,
-    ) -> crate::alloc::collections::btree_map::Entry<'_, Cow<'a, str>, Value<'a>> {
-        self.0.entry(key)
+    /// Get an iterator over the key-value pairs, sorted by key even if backed by a hash map.
+    #[cfg(not(feature = "hashbrown"))]
+    pub(crate) fn sorted_iter(&self) -> Iter<'_, 'a> {
+        self.iter()
     }
 
-    pub(crate) fn get_mut(&mut self, key: &str) -> Option<&mut Value<'a>> {
-        self.0.get_mut(key)
+    /// Get an iterator over the key-value pairs, sorted by key even if backed by a hash map.
+    #[cfg(feature = "hashbrown")]
+    pub(crate) fn sorted_iter(&self) -> alloc::vec::IntoIter<(&Cow<'a, str>, &Value<'a>)> {
+        let mut entries = self.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(k, _)| *k);
+
+        entries.into_iter()
+    }
+
+    /// Get an iterator over the key-value pairs, with mutable values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, 'a> {
+        IterMut::new(self)
+    }
+
+    /// Call `f` on every value in this table, recursively.
+    ///
+    /// See [`Value::visit`] for details. Unlike on a [`Value`], the table itself isn't visited.
+    pub fn visit<F>(&self, f: &mut F)
+    where
+        F: FnMut(&[Cow<'a, str>], &Value<'a>),
+    {
+        let mut path = Vec::new();
+        for (key, value) in self.iter() {
+            path.push(key.clone());
+            crate::value::visit(value, &mut path, f);
+            path.pop();
+        }
+    }
+
+    /// Call `f` on every value in this table, recursively and mutably.
+    ///
+    /// See [`Value::visit_mut`] for details. Unlike on a [`Value`], the table itself isn't visited.
+    pub fn visit_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&[Cow<'a, str>], &mut Value<'a>),
+    {
+        let mut path = Vec::new();
+        for (key, value) in self.iter_mut() {
+            path.push(key.clone());
+            crate::value::visit_mut(value, &mut path, f);
+            path.pop();
+        }
+    }
+
+    /// Deep-merge `other` into this table.
+    ///
+    /// Tables present in both are merged recursively. Any other value in `other`, including
+    /// arrays, replaces the one in this table. This is the same as calling [`Table::merge_with`]
+    /// with the default [`MergePolicy`].
+    pub fn merge(&mut self, other: Table<'a>) {
+        self.merge_with(other, MergePolicy::default());
+    }
+
+    /// Deep-merge `other` into this table, using `policy` to resolve conflicts.
+    ///
+    /// Tables present in both are always merged recursively.
+    pub fn merge_with(&mut self, other: Table<'a>, policy: MergePolicy) {
+        for (key, value) in other {
+            let existing = match self.0.entry(key) {
+                map::Entry::Vacant(entry) => {
+                    entry.insert(value);
+                    continue;
+                }
+                map::Entry::Occupied(entry) => entry.into_mut(),
+            };
+
+            match (existing, value) {
+                (Value::Table(existing), Value::Table(table)) => {
+                    existing.merge_with(table, policy);
+                }
+                (Value::Array(existing), Value::Array(array))
+                    if policy.arrays == ArrayMerge::Append =>
+                {
+                    existing.extend(array);
+                }
+                (existing, value) => {
+                    if policy.conflicts == ConflictMerge::Override {
+                        *existing = value;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Convert into a table that owns all its data.
+    ///
+    /// Common keys, e.g `version` or `features`, are shared static strings instead of being
+    /// allocated for each table.
+    pub fn into_owned(self) -> Table<'static> {
+        Table(
+            self.0
+                .into_iter()
+                .map(|(k, v)| (owned_key(k), v.into_owned()))
+                .collect(),
+        )
+    }
+
+    /// Get the entry for `key`, to insert or modify its value in place.
+    ///
+    /// ```
+    /// use tomling::{parse, Value};
+    ///
+    /// let mut table = parse("count = 1").unwrap();
+    /// for key in ["count", "other"] {
+    ///     table
+    ///         .entry(key.into())
+    ///         .and_modify(|v| {
+    ///             if let Value::Integer(i) = v {
+    ///                 *i += 1;
+    ///             }
+    ///         })
+    ///         .or_insert(Value::Integer(0));
+    /// }
+    /// assert_eq!(table.get("count"), Some(&Value::Integer(2)));
+    /// assert_eq!(table.get("other"), Some(&Value::Integer(0)));
+    /// ```
+    pub fn entry(&mut self, key: Cow<'a, str>) -> Entry<'_, 'a> {
+        Entry(self.0.entry(key))
+    }
+
+    /// Get the value for `key`, inserting the result of `default` first if there's none.
+    ///
+    /// A shorthand for `table.entry(key).or_insert_with(default)`.
+    pub fn get_or_insert_with<F>(&mut self, key: Cow<'a, str>, default: F) -> &mut Value<'a>
+    where
+        F: FnOnce() -> Value<'a>,
+    {
+        self.entry(key).or_insert_with(default)
+    }
+}
+
+/// An entry of a [`Table`], which may or may not have a value.
+///
+/// Created by [`Table::entry`].
+#[derive(Debug)]
+pub struct Entry<'t, 'a>(MapEntry<'t, 'a>);
+
+impl<'t, 'a> Entry<'t, 'a> {
+    /// The key of the entry.
+    pub fn key(&self) -> &str {
+        self.0.key()
+    }
+
+    /// Insert `default` if the entry has no value, and return the value.
+    pub fn or_insert(self, default: Value<'a>) -> &'t mut Value<'a> {
+        self.0.or_insert(default)
+    }
+
+    /// Insert the result of `default` if the entry has no value, and return the value.
+    pub fn or_insert_with<F>(self, default: F) -> &'t mut Value<'a>
+    where
+        F: FnOnce() -> Value<'a>,
+    {
+        self.0.or_insert_with(default)
+    }
+
+    /// Call `f` on the value, if the entry has one.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Value<'a>),
+    {
+        Self(self.0.and_modify(f))
+    }
+}
+
+/// How [`Table::merge_with`] resolves conflicts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MergePolicy {
+    /// How arrays present in both tables are merged.
+    pub arrays: ArrayMerge,
+    /// How other conflicting values are resolved, including arrays unless they're appended.
+    pub conflicts: ConflictMerge,
+}
+
+/// How [`Table::merge_with`] merges arrays present in both tables.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayMerge {
+    /// The array is resolved like any other conflicting value.
+    #[default]
+    Replace,
+    /// The elements of the other array are appended to the existing array.
+    Append,
+}
+
+/// How [`Table::merge_with`] resolves conflicting values, other than tables.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictMerge {
+    /// The value from the other table replaces the existing one.
+    #[default]
+    Override,
+    /// The existing value is kept.
+    Keep,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Table<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl FromStr for Table<'static> {
+    type Err = Error;
+
+    /// Parse a TOML document, see [`crate::parse`].
+    ///
+    /// Since the table can't borrow from `s`, all strings are copied.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        crate::parse(s).map(Table::into_owned)
     }
 }
 
@@ -62,10 +301,22 @@ impl<'a> FromIterator<(Cow<'a, str>, Value<'a>)> for Table<'a> {
     }
 }
 
+impl<'a> Extend<(Cow<'a, str>, Value<'a>)> for Table<'a> {
+    /// Insert all the key-value pairs, replacing any existing values for the keys.
+    ///
+    /// Unlike [`Table::merge`], tables present in both aren't merged.
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (Cow<'a, str>, Value<'a>)>,
+    {
+        self.0.extend(iter)
+    }
+}
+
 /// An iterator over the key-value pairs of a table.
 #[derive(Debug)]
 pub struct Iter<'i, 'a> {
-    iter: alloc::collections::btree_map::Iter<'i, Cow<'a, str>, Value<'a>>,
+    iter: map::Iter<'i, Cow<'a, str>, Value<'a>>,
 }
 
 impl<'t, 'a> Iter<'t, 'a> {
@@ -84,6 +335,28 @@ impl<'i, 'a> Iterator for Iter<'i, 'a> {
     }
 }
 
+/// An iterator over the key-value pairs of a table, with mutable values.
+#[derive(Debug)]
+pub struct IterMut<'i, 'a> {
+    iter: map::IterMut<'i, Cow<'a, str>, Value<'a>>,
+}
+
+impl<'t, 'a> IterMut<'t, 'a> {
+    fn new(table: &'t mut Table<'a>) -> IterMut<'t, 'a> {
+        IterMut {
+            iter: table.0.iter_mut(),
+        }
+    }
+}
+
+impl<'i, 'a> Iterator for IterMut<'i, 'a> {
+    type Item = (&'i Cow<'a, str>, &'i mut Value<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
 impl<'a> IntoIterator for Table<'a> {
     type Item = (Cow<'a, str>, Value<'a>);
     type IntoIter = IntoIter<'a>;
@@ -98,7 +371,7 @@ impl<'a> IntoIterator for Table<'a> {
 /// An iterator over the key-value pairs of a table that moves out of the `Table`.
 #[derive(Debug)]
 pub struct IntoIter<'a> {
-    iter: alloc::collections::btree_map::IntoIter<Cow<'a, str>, Value<'a>>,
+    iter: map::IntoIter<Cow<'a, str>, Value<'a>>,
 }
 
 impl<'a> Iterator for IntoIter<'a> {
@@ -108,3 +381,301 @@ impl<'a> Iterator for IntoIter<'a> {
         self.iter.next()
     }
 }
+
+/// Keys that are common in TOML documents, `Cargo.toml` files in particular. Sorted.
+const COMMON_KEYS: &[&str] = &[
+    "authors",
+    "bench",
+    "bin",
+    "build",
+    "build-dependencies",
+    "categories",
+    "default",
+    "default-features",
+    "dependencies",
+    "description",
+    "dev-dependencies",
+    "documentation",
+    "edition",
+    "example",
+    "features",
+    "git",
+    "homepage",
+    "keywords",
+    "lib",
+    "license",
+    "name",
+    "optional",
+    "package",
+    "path",
+    "publish",
+    "readme",
+    "repository",
+    "rust-version",
+    "target",
+    "test",
+    "version",
+    "workspace",
+];
+
+/// Convert `key` into an owned key, without allocating if it's one of [`COMMON_KEYS`].
+fn owned_key(key: Cow<'_, str>) -> Cow<'static, str> {
+    match COMMON_KEYS.binary_search(&&*key) {
+        Ok(i) => Cow::Borrowed(COMMON_KEYS[i]),
+        Err(_) => Cow::Owned(key.into_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArrayMerge, ConflictMerge, MergePolicy};
+    use crate::{parse, Value};
+
+    const DEFAULTS: &str = r#"
+        name = "default"
+        ports = [80]
+        [server]
+        host = "localhost"
+        timeout = 30
+        [server.tls]
+        enabled = false
+    "#;
+    const OVERRIDES: &str = r#"
+        ports = [443]
+        debug = true
+        [server]
+        timeout = 60
+        [server.tls]
+        enabled = true
+    "#;
+
+    #[test]
+    fn merge() {
+        let mut table = parse(DEFAULTS).unwrap();
+        table.merge(parse(OVERRIDES).unwrap());
+        let expected = parse(
+            r#"
+            name = "default"
+            ports = [443]
+            debug = true
+            [server]
+            host = "localhost"
+            timeout = 60
+            [server.tls]
+            enabled = true
+        "#,
+        )
+        .unwrap();
+        assert_eq!(table, expected);
+
+        // Tables and other values replace each other.
+        let mut table = parse("a = 1\nb.c = 1").unwrap();
+        table.merge(parse("a.b = 1\nb = 2").unwrap());
+        assert_eq!(table, parse("a.b = 1\nb = 2").unwrap());
+    }
+
+    #[test]
+    fn merge_with() {
+        let policy = MergePolicy {
+            arrays: ArrayMerge::Append,
+            conflicts: ConflictMerge::Keep,
+        };
+        let mut table = parse(DEFAULTS).unwrap();
+        table.merge_with(parse(OVERRIDES).unwrap(), policy);
+        let ports = [80, 443].into_iter().collect::<Value<'_>>();
+        assert_eq!(table.get("ports"), Some(&ports));
+        assert_eq!(table.get("debug"), Some(&Value::from(true)));
+        let server = table.get("server").and_then(Value::as_table).unwrap();
+        assert_eq!(server.get("timeout"), Some(&Value::from(30)));
+        let tls = server.get("tls").and_then(Value::as_table).unwrap();
+        assert_eq!(tls.get("enabled"), Some(&Value::from(false)));
+
+        let policy = MergePolicy {
+            arrays: ArrayMerge::Replace,
+            conflicts: ConflictMerge::Keep,
+        };
+        let mut table = parse(DEFAULTS).unwrap();
+        table.merge_with(parse(OVERRIDES).unwrap(), policy);
+        let ports = [80].into_iter().collect::<Value<'_>>();
+        assert_eq!(table.get("ports"), Some(&ports));
+    }
+
+    #[test]
+    fn iter_mut() {
+        fn bump(value: &mut Value<'_>) {
+            match value {
+                Value::Integer(i) => *i += 1,
+                Value::String(s) => *s = s.to_uppercase().into(),
+                Value::Array(array) => array.iter_mut().for_each(bump),
+                Value::Table(table) => table.iter_mut().for_each(|(_, v)| bump(v)),
+                _ => (),
+            }
+        }
+
+        let mut table = parse(DEFAULTS).unwrap();
+        table.iter_mut().for_each(|(_, v)| bump(v));
+        assert_eq!(table.get("name"), Some(&Value::from("DEFAULT")));
+        let ports = [81].into_iter().collect::<Value<'_>>();
+        assert_eq!(table.get("ports"), Some(&ports));
+        let server = table.get("server").and_then(Value::as_table).unwrap();
+        assert_eq!(server.get("host"), Some(&Value::from("LOCALHOST")));
+        assert_eq!(server.get("timeout"), Some(&Value::from(31)));
+    }
+
+    #[test]
+    fn visit_mut() {
+        let mut table = parse(DEFAULTS).unwrap();
+        let mut paths = alloc::vec::Vec::new();
+        table.visit_mut(&mut |path, value| {
+            paths.push(path.join("."));
+            if let Value::Integer(i) = value {
+                *i *= 2;
+            }
+        });
+        // Sorted, since the iteration order depends on the map backing tables.
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "name",
+                "ports",
+                "ports.0",
+                "server",
+                "server.host",
+                "server.timeout",
+                "server.tls",
+                "server.tls.enabled"
+            ]
+        );
+        let server = table.get("server").and_then(Value::as_table).unwrap();
+        assert_eq!(server.get("timeout"), Some(&Value::from(60)));
+
+        // Values are visited before their content, which can be replaced.
+        let mut value = Value::from(table);
+        let mut visited = 0;
+        value.visit_mut(&mut |path, value| {
+            visited += 1;
+            if path.len() == 1 && path[0] == "server" {
+                *value = Value::from(true);
+            }
+        });
+        assert_eq!(visited, 5);
+    }
+
+    #[test]
+    fn chained_builders() {
+        use crate::{Array, Table};
+
+        let mut ports = Array::with_capacity(2);
+        ports.push(80.into()).push(443.into());
+        let mut server = Table::new();
+        server
+            .insert("host".into(), "localhost".into())
+            .insert("timeout".into(), 30.into());
+        let mut table = Table::new();
+        table
+            .insert("name".into(), "default".into())
+            .insert("ports".into(), ports.into())
+            .insert("server".into(), server.into());
+
+        let expected = parse(
+            r#"
+            name = "default"
+            ports = [80, 443]
+            [server]
+            host = "localhost"
+            timeout = 30
+            "#,
+        )
+        .unwrap();
+        assert_eq!(table, expected);
+    }
+
+    #[test]
+    fn entry() {
+        use crate::Table;
+
+        let mut table = parse(DEFAULTS).unwrap();
+        let entry = table.entry("server".into());
+        assert_eq!(entry.key(), "server");
+        let server = entry.or_insert_with(|| unreachable!());
+        assert!(server.as_table().is_some());
+
+        let mut called = false;
+        table
+            .entry("client".into())
+            .and_modify(|_| called = true)
+            .or_insert_with(|| Table::new().into());
+        assert!(!called);
+        assert_eq!(table.get("client"), Some(&Value::from(Table::new())));
+
+        let mut table = Table::new();
+        for key in ["a", "b", "a"] {
+            if let Value::Integer(i) = table.get_or_insert_with(key.into(), || Value::Integer(0)) {
+                *i += 1;
+            }
+        }
+        assert_eq!(table.get("a"), Some(&Value::Integer(2)));
+        assert_eq!(table.get("b"), Some(&Value::Integer(1)));
+    }
+
+    #[test]
+    fn key_borrowing() {
+        use super::COMMON_KEYS;
+        use alloc::borrow::Cow;
+
+        let table = parse("bare = 1\n'literal' = 2\n\"basic\" = 3\n\"esc\\u0061ped\" = 4").unwrap();
+        fn borrowed<'t>(table: &'t super::Table<'_>) -> alloc::vec::Vec<(&'t str, bool)> {
+            let mut keys = table
+                .iter()
+                .map(|(k, _)| (&**k, matches!(k, Cow::Borrowed(_))))
+                .collect::<alloc::vec::Vec<_>>();
+            keys.sort_unstable();
+
+            keys
+        }
+        assert_eq!(
+            borrowed(&table),
+            [
+                ("bare", true),
+                ("basic", true),
+                ("escaped", false),
+                ("literal", true)
+            ]
+        );
+
+        assert!(COMMON_KEYS.windows(2).all(|w| w[0] < w[1]));
+        let table = parse("version = 1\n[dependencies]\nfoo = { features = [], bar = 1 }")
+            .unwrap()
+            .into_owned();
+        let foo = table
+            .get("dependencies")
+            .and_then(|d| d.get("foo"))
+            .and_then(Value::as_table)
+            .unwrap();
+        assert_eq!(borrowed(foo), [("bar", false), ("features", true)]);
+        assert_eq!(
+            borrowed(&table),
+            [("dependencies", true), ("version", true)]
+        );
+    }
+
+    #[test]
+    fn extend() {
+        let mut table = parse("a = [1]\nb = { c = 1 }").unwrap();
+        let other = parse("a = [2, 3]\nb = { d = 2 }\ne = true").unwrap();
+        table.extend(other);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get("e"), Some(&Value::from(true)));
+        // Tables aren't merged, unlike with `merge`.
+        let b = table.get("b").and_then(Value::as_table).unwrap();
+        assert_eq!(b.get("c"), None);
+        assert_eq!(b.get("d"), Some(&Value::from(2)));
+
+        let Some(Value::Array(a)) = table.get_mut("a") else {
+            panic!("`a` should be an array");
+        };
+        a.extend([4, 5].into_iter().map(Value::from));
+        assert_eq!(a.as_i64_vec(), Some(alloc::vec![2, 3, 4, 5]));
+    }
+}
@@ -1,14 +1,20 @@
 use crate::{datetime, Array, Date, Datetime, Table, Time};
-use alloc::{borrow::Cow, string::String, vec::Vec};
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
 /// A TOML value.
 #[derive(Debug, Clone, PartialEq)]
-// FIXME: Make this more efficient, by manually implementing `Deserialize``.
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
-#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum Value<'a> {
     /// A string.
-    #[cfg_attr(feature = "serde", serde(borrow))]
     String(Cow<'a, str>),
     /// An integer.
     Integer(i64),
@@ -25,8 +31,126 @@ pub enum Value<'a> {
 }
 
 impl<'a> Value<'a> {
+    /// The kind of the value.
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::String(_) => ValueKind::String,
+            Value::Integer(_) => ValueKind::Integer,
+            Value::Float(_) => ValueKind::Float,
+            Value::Boolean(_) => ValueKind::Boolean,
+            Value::Array(_) => ValueKind::Array,
+            Value::Table(_) => ValueKind::Table,
+            Value::Datetime(_) => ValueKind::Datetime,
+        }
+    }
+
+    /// The name of the type of the value (e.g `"integer"`), for use in messages.
+    pub fn type_name(&self) -> &'static str {
+        self.kind().name()
+    }
+
+    /// Convert into a value that owns all its data.
+    pub fn into_owned(self) -> Value<'static> {
+        match self {
+            Value::String(s) => Value::String(Cow::Owned(s.into_owned())),
+            Value::Integer(i) => Value::Integer(i),
+            Value::Float(f) => Value::Float(f),
+            Value::Boolean(b) => Value::Boolean(b),
+            Value::Array(array) => Value::Array(array.into_owned()),
+            Value::Table(table) => Value::Table(table.into_owned()),
+            Value::Datetime(dt) => Value::Datetime(dt),
+        }
+    }
+
+    /// Call `f` on this value and, recursively, on every value nested in it.
+    ///
+    /// Values are visited before the values nested in them, along with their path from this value.
+    /// Array elements have their index as path segment.
+    ///
+    /// ```
+    /// use tomling::{parse, Value};
+    ///
+    /// let table = parse(r#"a = [{ path = "x" }, { b = { path = "y" } }]"#).unwrap();
+    /// let mut paths = Vec::new();
+    /// Value::from(table).visit(&mut |path, value| {
+    ///     if path.last().map_or(false, |k| k == "path") {
+    ///         paths.push((path.join("."), value.as_str().unwrap().to_string()));
+    ///     }
+    /// });
+    /// assert_eq!(paths, [("a.0.path".into(), "x".into()), ("a.1.b.path".into(), "y".into())]);
+    /// ```
+    pub fn visit<F>(&self, f: &mut F)
+    where
+        F: FnMut(&[Cow<'a, str>], &Value<'a>),
+    {
+        visit(self, &mut Vec::new(), f);
+    }
+
+    /// Call `f` on this value and, recursively, on every value nested in it, mutably.
+    ///
+    /// Values are visited before the values nested in them, so `f` can modify a table or array
+    /// before its (new) content is visited. See [`Value::visit`] for details.
+    pub fn visit_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&[Cow<'a, str>], &mut Value<'a>),
+    {
+        visit_mut(self, &mut Vec::new(), f);
+    }
+
+    /// Index into a table by key, or into an array by position.
+    ///
+    /// Returns `None` if the value isn't a table (for `&str` indexes) or an array (for `usize`
+    /// indexes), or if the key or position doesn't exist.
+    ///
+    /// ```
+    /// use tomling::{parse, Value};
+    ///
+    /// let value = Value::from(parse(r#"a = [{ b = 1 }]"#).unwrap());
+    /// let b = value.get("a").and_then(|a| a.get(0)).and_then(|t| t.get("b"));
+    /// assert_eq!(b, Some(&Value::Integer(1)));
+    /// assert!(value.get(0).is_none());
+    /// ```
+    pub fn get<I: ValueIndex>(&self, index: I) -> Option<&Value<'a>> {
+        index.index_into(self)
+    }
+
+    /// Look up a nested value by a `/`-separated path, e.g `"/a/0/b"`.
+    ///
+    /// Segments are table keys, or positions for arrays. As in JSON pointers, `~1` and `~0` in a
+    /// segment stand for `/` and `~` respectively. The empty path is the value itself. Returns
+    /// `None` if the path doesn't start with a `/` or doesn't lead to a value.
+    ///
+    /// ```
+    /// use tomling::{parse, Value};
+    ///
+    /// let value = Value::from(parse(r#"a = [{ "b/c" = 1 }]"#).unwrap());
+    /// assert_eq!(value.pointer("/a/0/b~1c"), Some(&Value::Integer(1)));
+    /// assert!(value.pointer("/a/1").is_none());
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value<'a>> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .try_fold(self, |value, segment| {
+                let segment = if segment.contains('~') {
+                    Cow::Owned(segment.replace("~1", "/").replace("~0", "~"))
+                } else {
+                    Cow::Borrowed(segment)
+                };
+                match value {
+                    Value::Table(table) => table.get(&segment),
+                    Value::Array(array) => array.get(parse_array_index(&segment)?),
+                    _ => None,
+                }
+            })
+    }
+
     /// Returns the underlying `&str` if the `Value` is a string
-    pub fn as_str(&'a self) -> Option<&'a str> {
+    pub fn as_str(&self) -> Option<&str> {
         match self {
             Self::String(s) => Some(s),
             _ => None,
@@ -58,7 +182,15 @@ impl<'a> Value<'a> {
     }
 
     /// Returns the underlying [`Array`] if the `Value` is an array
-    pub fn as_array(&'a self) -> Option<&'a Array<'a>> {
+    pub fn as_array(&self) -> Option<&Array<'a>> {
+        match self {
+            Self::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying [`Array`] mutably if the `Value` is an array
+    pub fn as_array_mut(&mut self) -> Option<&mut Array<'a>> {
         match self {
             Self::Array(a) => Some(a),
             _ => None,
@@ -66,7 +198,15 @@ impl<'a> Value<'a> {
     }
 
     /// Returns the underlying [`Table`] if the `Value` is a table
-    pub fn as_table(&'a self) -> Option<&'a Table<'a>> {
+    pub fn as_table(&self) -> Option<&Table<'a>> {
+        match self {
+            Self::Table(t) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying [`Table`] mutably if the `Value` is a table
+    pub fn as_table_mut(&mut self) -> Option<&mut Table<'a>> {
         match self {
             Self::Table(t) => Some(t),
             _ => None,
@@ -83,6 +223,466 @@ impl<'a> Value<'a> {
     }
 }
 
+impl Value<'_> {
+    /// Compare two values for equality, using [`f64::total_cmp`] for floats.
+    ///
+    /// Unlike `==`, this is reflexive: `NaN` is equal to itself. However, `0.0` and `-0.0` are
+    /// not equal.
+    pub fn eq_total(&self, other: &Value<'_>) -> bool {
+        self.cmp_total(other) == Ordering::Equal
+    }
+
+    /// Compare two values, using [`f64::total_cmp`] for floats.
+    ///
+    /// Values of different types are ordered by type, in the order of the [`Value`] variants.
+    /// Arrays are compared lexicographically, and so are tables, entry by entry in key order.
+    pub fn cmp_total(&self, other: &Value<'_>) -> Ordering {
+        match (self, other) {
+            (Self::String(a), Value::String(b)) => a.cmp(b),
+            (Self::Integer(a), Value::Integer(b)) => a.cmp(b),
+            (Self::Float(a), Value::Float(b)) => a.total_cmp(b),
+            (Self::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+            (Self::Array(a), Value::Array(b)) => cmp_seq(a.iter(), b.iter(), |a, b| a.cmp_total(b)),
+            (Self::Table(a), Value::Table(b)) => {
+                cmp_seq(a.sorted_iter(), b.sorted_iter(), |(ak, av), (bk, bv)| {
+                    ak.cmp(bk).then_with(|| av.cmp_total(bv))
+                })
+            }
+            (Self::Datetime(a), Value::Datetime(b)) => a.cmp(b),
+            _ => self.kind().cmp(&other.kind()),
+        }
+    }
+
+    /// Feed the value into `state`, consistently with [`Value::eq_total`].
+    fn hash_total<H: Hasher>(&self, state: &mut H) {
+        self.kind().hash(state);
+        match self {
+            Self::String(s) => s.hash(state),
+            Self::Integer(i) => i.hash(state),
+            Self::Float(f) => f.to_bits().hash(state),
+            Self::Boolean(b) => b.hash(state),
+            Self::Array(array) => {
+                array.len().hash(state);
+                for value in array.iter() {
+                    value.hash_total(state);
+                }
+            }
+            Self::Table(table) => {
+                table.len().hash(state);
+                for (key, value) in table.sorted_iter() {
+                    key.hash(state);
+                    value.hash_total(state);
+                }
+            }
+            Self::Datetime(dt) => dt.hash(state),
+        }
+    }
+}
+
+/// A type that can index into a [`Value`], see [`Value::get`].
+///
+/// Implemented for `str` and `String` to index into tables, and `usize` to index into arrays. This
+/// trait is sealed and can't be implemented outside of this crate.
+pub trait ValueIndex: private::Sealed {
+    /// Get the value at this index in `value`, if any.
+    fn index_into<'v, 'a>(&self, value: &'v Value<'a>) -> Option<&'v Value<'a>>;
+}
+
+impl ValueIndex for usize {
+    fn index_into<'v, 'a>(&self, value: &'v Value<'a>) -> Option<&'v Value<'a>> {
+        value.as_array()?.get(*self)
+    }
+}
+
+impl ValueIndex for str {
+    fn index_into<'v, 'a>(&self, value: &'v Value<'a>) -> Option<&'v Value<'a>> {
+        value.as_table()?.get(self)
+    }
+}
+
+impl ValueIndex for String {
+    fn index_into<'v, 'a>(&self, value: &'v Value<'a>) -> Option<&'v Value<'a>> {
+        self.as_str().index_into(value)
+    }
+}
+
+impl<T> ValueIndex for &T
+where
+    T: ValueIndex + ?Sized,
+{
+    fn index_into<'v, 'a>(&self, value: &'v Value<'a>) -> Option<&'v Value<'a>> {
+        (**self).index_into(value)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for usize {}
+    impl Sealed for str {}
+    impl Sealed for alloc::string::String {}
+    impl<T> Sealed for &T where T: Sealed + ?Sized {}
+}
+
+/// Parse an array index of a pointer segment, rejecting leading zeros and signs.
+fn parse_array_index(segment: &str) -> Option<usize> {
+    if segment.starts_with('+') || (segment.starts_with('0') && segment.len() > 1) {
+        return None;
+    }
+
+    segment.parse().ok()
+}
+
+/// The kind of a [`Value`], without its contents.
+///
+/// Kinds are ordered in the order of the [`Value`] variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ValueKind {
+    /// A string.
+    String,
+    /// An integer.
+    Integer,
+    /// A floating-point number.
+    Float,
+    /// A boolean.
+    Boolean,
+    /// An array.
+    Array,
+    /// A table.
+    Table,
+    /// A date and time.
+    Datetime,
+}
+
+impl ValueKind {
+    /// The name of the kind (e.g `"integer"`), for use in messages.
+    pub fn name(self) -> &'static str {
+        match self {
+            ValueKind::String => "string",
+            ValueKind::Integer => "integer",
+            ValueKind::Float => "float",
+            ValueKind::Boolean => "boolean",
+            ValueKind::Array => "array",
+            ValueKind::Table => "table",
+            ValueKind::Datetime => "datetime",
+        }
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Visit `value`, at `path`, and the values nested in it.
+pub(crate) fn visit<'a, F>(value: &Value<'a>, path: &mut Vec<Cow<'a, str>>, f: &mut F)
+where
+    F: FnMut(&[Cow<'a, str>], &Value<'a>),
+{
+    f(path, value);
+    match value {
+        Value::Array(array) => {
+            for (i, value) in array.iter().enumerate() {
+                path.push(Cow::Owned(i.to_string()));
+                visit(value, path, f);
+                path.pop();
+            }
+        }
+        Value::Table(table) => {
+            for (key, value) in table.iter() {
+                path.push(key.clone());
+                visit(value, path, f);
+                path.pop();
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Visit `value`, at `path`, and the values nested in it, mutably.
+pub(crate) fn visit_mut<'a, F>(value: &mut Value<'a>, path: &mut Vec<Cow<'a, str>>, f: &mut F)
+where
+    F: FnMut(&[Cow<'a, str>], &mut Value<'a>),
+{
+    f(path, value);
+    match value {
+        Value::Array(array) => {
+            for (i, value) in array.iter_mut().enumerate() {
+                path.push(Cow::Owned(i.to_string()));
+                visit_mut(value, path, f);
+                path.pop();
+            }
+        }
+        Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                path.push(key.clone());
+                visit_mut(value, path, f);
+                path.pop();
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Lexicographically compare two sequences, using `cmp` for the elements.
+fn cmp_seq<T>(
+    a: impl IntoIterator<Item = T>,
+    b: impl IntoIterator<Item = T>,
+    mut cmp: impl FnMut(T, T) -> Ordering,
+) -> Ordering {
+    let mut b = b.into_iter();
+    for a in a {
+        let Some(b) = b.next() else {
+            return Ordering::Greater;
+        };
+        match cmp(a, b) {
+            Ordering::Equal => (),
+            ordering => return ordering,
+        }
+    }
+
+    match b.next() {
+        Some(_) => Ordering::Less,
+        None => Ordering::Equal,
+    }
+}
+
+/// A [`Value`] wrapper implementing [`Eq`], [`Ord`] and [`Hash`].
+///
+/// Floats are compared using [`f64::total_cmp`], so this can be used to sort or deduplicate
+/// values, or as the key of a map. See [`Value::cmp_total`] for details on the ordering.
+#[derive(Debug, Clone)]
+pub struct OrderedValue<'a>(pub Value<'a>);
+
+impl PartialEq for OrderedValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_total(&other.0)
+    }
+}
+
+impl Eq for OrderedValue<'_> {}
+
+impl PartialOrd for OrderedValue<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedValue<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_total(&other.0)
+    }
+}
+
+impl Hash for OrderedValue<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash_total(state);
+    }
+}
+
+impl FromStr for Value<'static> {
+    type Err = crate::Error;
+
+    /// Parse a standalone TOML value, e.g `[1, 2, 3]` or `{ a = "b" }`.
+    ///
+    /// Since the value can't borrow from `s`, all strings are copied.
+    fn from_str(s: &str) -> Result<Self, crate::Error> {
+        crate::parse::parse_standalone_value(s).map(Value::into_owned)
+    }
+}
+
+impl<'a> From<Value<'a>> for OrderedValue<'a> {
+    fn from(value: Value<'a>) -> Self {
+        Self(value)
+    }
+}
+
+/// Formats the value as a TOML fragment, with tables formatted as inline tables.
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(s) => write_string(f, s),
+            Self::Integer(i) => write!(f, "{i}"),
+            Self::Float(n) if n.is_nan() => write!(f, "nan"),
+            Self::Float(n) if n.is_infinite() => {
+                write!(f, "{}inf", if n.is_sign_negative() { "-" } else { "" })
+            }
+            // Unlike `Display`, `Debug` always emits a fractional part or an exponent. Both use the
+            // shortest representation that round-trips.
+            Self::Float(n) => write!(f, "{n:?}"),
+            Self::Boolean(b) => write!(f, "{b}"),
+            Self::Array(array) => {
+                f.write_char('[')?;
+                for (i, value) in array.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Self::Table(table) if table.is_empty() => f.write_str("{}"),
+            Self::Table(table) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in table.iter().enumerate() {
+                    if i != 0 {
+                        f.write_char(',')?;
+                    }
+                    f.write_char(' ')?;
+                    write_key(f, key)?;
+                    write!(f, " = {value}")?;
+                }
+                f.write_str(" }")
+            }
+            Self::Datetime(dt) => write!(f, "{dt}"),
+        }
+    }
+}
+
+/// Write a key, quoting it if it can't be a bare key.
+fn write_key(f: &mut fmt::Formatter<'_>, key: &str) -> fmt::Result {
+    let is_bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_bare {
+        f.write_str(key)
+    } else {
+        write_string(f, key)
+    }
+}
+
+/// Write a basic string, escaping as needed.
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\u{08}' => f.write_str("\\b")?,
+            '\t' => f.write_str("\\t")?,
+            '\n' => f.write_str("\\n")?,
+            '\u{0c}' => f.write_str("\\f")?,
+            '\r' => f.write_str("\\r")?,
+            '\0'..='\u{1f}' | '\u{7f}' => write!(f, "\\u{:04X}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Datetimes are serialized the same as [`Datetime`] itself.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Value<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Value::String(s) => serializer.serialize_str(s),
+            Value::Integer(i) => serializer.serialize_i64(*i),
+            Value::Float(f) => serializer.serialize_f64(*f),
+            Value::Boolean(b) => serializer.serialize_bool(*b),
+            Value::Array(array) => array.serialize(serializer),
+            Value::Table(table) => table.serialize(serializer),
+            Value::Datetime(dt) => dt.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, 'de: 'a> serde::Deserialize<'de> for Value<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Value<'a>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor(core::marker::PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ValueVisitor<'a>(core::marker::PhantomData<Value<'a>>);
+
+#[cfg(feature = "serde")]
+impl<'a, 'de: 'a> serde::de::Visitor<'de> for ValueVisitor<'a> {
+    type Value = Value<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a TOML value")
+    }
+
+    fn visit_bool<E>(self, b: bool) -> Result<Value<'a>, E> {
+        Ok(Value::Boolean(b))
+    }
+
+    fn visit_i64<E>(self, i: i64) -> Result<Value<'a>, E> {
+        Ok(Value::Integer(i))
+    }
+
+    fn visit_u64<E>(self, u: u64) -> Result<Value<'a>, E>
+    where
+        E: serde::de::Error,
+    {
+        i64::try_from(u)
+            .map(Value::Integer)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Unsigned(u), &self))
+    }
+
+    fn visit_f64<E>(self, f: f64) -> Result<Value<'a>, E> {
+        Ok(Value::Float(f))
+    }
+
+    fn visit_borrowed_str<E>(self, s: &'de str) -> Result<Value<'a>, E> {
+        Ok(Value::String(Cow::Borrowed(s)))
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Value<'a>, E> {
+        Ok(Value::String(Cow::Owned(s.into())))
+    }
+
+    fn visit_string<E>(self, s: String) -> Result<Value<'a>, E> {
+        Ok(Value::String(Cow::Owned(s)))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Value<'a>, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut array = Array::new();
+        while let Some(value) = seq.next_element()? {
+            array.push(value);
+        }
+
+        Ok(Value::Array(array))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Value<'a>, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(transparent)]
+        struct Key<'k>(#[serde(borrow)] Cow<'k, str>);
+
+        let mut table = Table::new();
+        let Some(Key(key)) = map.next_key()? else {
+            return Ok(Value::Table(table));
+        };
+        // Native datetimes are deserialized as a map, tagged by a leading marker key.
+        if key == crate::serde::DATETIME_MARKER {
+            map.next_value::<serde::de::IgnoredAny>()?;
+            let map = serde::de::value::MapAccessDeserializer::new(map);
+            return serde::Deserialize::deserialize(map).map(Value::Datetime);
+        }
+        table.insert(key, map.next_value()?);
+        while let Some((Key(key), value)) = map.next_entry()? {
+            table.insert(key, value);
+        }
+
+        Ok(Value::Table(table))
+    }
+}
+
 impl<'a, V> FromIterator<V> for Value<'a>
 where
     V: Into<Value<'a>>,
@@ -129,6 +729,47 @@ impl_from!(Date => Datetime);
 impl_from!(Time => Datetime);
 impl_from!(datetime::Offset => Datetime);
 
+macro_rules! impl_partial_eq {
+    ($ty:ty => $variant:ident) => {
+        impl PartialEq<$ty> for Value<'_> {
+            fn eq(&self, other: &$ty) -> bool {
+                matches!(self, Value::$variant(value) if value == other)
+            }
+        }
+    };
+}
+
+impl_partial_eq!(str => String);
+impl_partial_eq!(&str => String);
+impl_partial_eq!(String => String);
+impl_partial_eq!(i64 => Integer);
+impl_partial_eq!(f64 => Float);
+impl_partial_eq!(bool => Boolean);
+impl_partial_eq!(Datetime => Datetime);
+
+impl<'a, T> PartialEq<[T]> for Value<'a>
+where
+    Value<'a>: PartialEq<T>,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        match self {
+            Value::Array(array) => {
+                array.len() == other.len() && array.iter().zip(other).all(|(v, o)| v == o)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> PartialEq<[T; N]> for Value<'a>
+where
+    Value<'a>: PartialEq<T>,
+{
+    fn eq(&self, other: &[T; N]) -> bool {
+        <Self as PartialEq<[T]>>::eq(self, other)
+    }
+}
+
 macro_rules! impl_try_from {
     ($variant:ident => $ty:ty) => {
         impl<'a> TryFrom<Value<'a>> for $ty {
@@ -140,6 +781,7 @@ macro_rules! impl_try_from {
                     _ => Err(crate::Error::Convert {
                         from: "tomling::Value",
                         to: stringify!($ty),
+                        path: None,
                     }),
                 }
             }
@@ -164,6 +806,7 @@ impl<'a> TryFrom<Value<'a>> for &'a str {
             _ => Err(crate::Error::Convert {
                 from: "tomling::Value",
                 to: "&str",
+                path: None,
             }),
         }
     }
@@ -180,6 +823,7 @@ macro_rules! impl_try_from_ref {
                     _ => Err(crate::Error::Convert {
                         from: "tomling::Value",
                         to: stringify!($ty),
+                        path: None,
                     }),
                 }
             }
@@ -196,6 +840,67 @@ impl_try_from_ref!(Array => Array<'b>);
 impl_try_from_ref!(Table => Table<'b>);
 impl_try_from_ref!(Datetime => Datetime);
 
+macro_rules! impl_try_from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl<'a> TryFrom<Value<'a>> for $ty {
+                type Error = crate::Error;
+
+                fn try_from(value: Value<'a>) -> Result<Self, Self::Error> {
+                    Self::try_from(&value)
+                }
+            }
+
+            impl<'a, 'b> TryFrom<&'a Value<'b>> for $ty {
+                type Error = crate::Error;
+
+                fn try_from(value: &'a Value<'b>) -> Result<Self, Self::Error> {
+                    // Fails if the value isn't an integer or it's out of range for the type.
+                    value
+                        .as_i64()
+                        .and_then(|i| <$ty>::try_from(i).ok())
+                        .ok_or(crate::Error::Convert {
+                            from: "tomling::Value",
+                            to: stringify!($ty),
+                            path: None,
+                        })
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_int!(i8, i16, i32, isize, u8, u16, u32, u64, usize);
+
+/// Converts a float value to the nearest `f32`, losing precision.
+///
+/// Fails if the value isn't a float, or if it's a finite float out of the range of `f32`.
+impl<'a> TryFrom<Value<'a>> for f32 {
+    type Error = crate::Error;
+
+    fn try_from(value: Value<'a>) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// Converts a float value to the nearest `f32`, losing precision.
+///
+/// Fails if the value isn't a float, or if it's a finite float out of the range of `f32`.
+impl<'a, 'b> TryFrom<&'a Value<'b>> for f32 {
+    type Error = crate::Error;
+
+    fn try_from(value: &'a Value<'b>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Float(f) if !f.is_finite() || (*f as f32).is_finite() => Ok(*f as f32),
+            _ => Err(crate::Error::Convert {
+                from: "tomling::Value",
+                to: "f32",
+                path: None,
+            }),
+        }
+    }
+}
+
 impl<'value, T> TryFrom<Value<'value>> for Vec<T>
 where
     T: TryFrom<Value<'value>, Error = crate::Error>,
@@ -208,7 +913,508 @@ where
             _ => Err(crate::Error::Convert {
                 from: "tomling::Value",
                 to: "Vec<T>",
+                path: None,
             }),
         }
     }
 }
+
+impl<'value, T, const N: usize> TryFrom<Value<'value>> for [T; N]
+where
+    T: TryFrom<Value<'value>, Error = crate::Error>,
+{
+    type Error = crate::Error;
+
+    fn try_from(value: Value<'value>) -> Result<Self, Self::Error> {
+        // Fails if the array doesn't have exactly `N` elements.
+        Vec::<T>::try_from(value)?
+            .try_into()
+            .map_err(|_| crate::Error::Convert {
+                from: "tomling::Value",
+                to: "[T; N]",
+                path: None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrderedValue, Value, ValueKind};
+    use alloc::{collections::BTreeSet, vec, vec::Vec};
+
+    #[test]
+    fn kind() {
+        let table = [("a", 1)].into_iter().collect::<Value<'_>>();
+        assert_eq!(table.kind(), ValueKind::Table);
+        assert_eq!(table.type_name(), "table");
+        assert_eq!(Value::from(1).type_name(), "integer");
+        assert_eq!(Value::from(1.0).type_name(), "float");
+        assert_eq!(
+            alloc::format!("expected table, found {}", Value::from(true).kind()),
+            "expected table, found boolean"
+        );
+    }
+
+    #[test]
+    fn try_from_int() {
+        use crate::Error;
+
+        assert_eq!(u16::try_from(Value::from(8080)).unwrap(), 8080);
+        assert_eq!(i8::try_from(&Value::from(-128)).unwrap(), -128);
+        assert_eq!(usize::try_from(&Value::from(0)).unwrap(), 0);
+        assert_eq!(
+            u64::try_from(Value::from(i64::MAX)).unwrap(),
+            i64::MAX as u64
+        );
+
+        for (result, to) in [
+            (u16::try_from(Value::from(65536)).map(|_| ()), "u16"),
+            (u32::try_from(Value::from(-1)).map(|_| ()), "u32"),
+            (i8::try_from(&Value::from(128)).map(|_| ()), "i8"),
+            (usize::try_from(&Value::from(1.0)).map(|_| ()), "usize"),
+        ] {
+            match result {
+                Err(Error::Convert { to: t, .. }) => assert_eq!(t, to),
+                r => panic!("unexpected result: {r:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn try_from_f32_and_array() {
+        assert_eq!(f32::try_from(Value::from(0.1)).unwrap(), 0.1_f32);
+        assert!(f32::try_from(&Value::from(f64::NAN)).unwrap().is_nan());
+        assert_eq!(
+            f32::try_from(&Value::from(f64::NEG_INFINITY)).unwrap(),
+            f32::NEG_INFINITY
+        );
+        f32::try_from(Value::from(1e300)).unwrap_err();
+        f32::try_from(Value::from(1)).unwrap_err();
+
+        let color = [255, 128, 0].into_iter().collect::<Value<'_>>();
+        assert_eq!(<[u8; 3]>::try_from(color.clone()).unwrap(), [255, 128, 0]);
+        <[u8; 4]>::try_from(color.clone()).unwrap_err();
+        <[u8; 2]>::try_from(color).unwrap_err();
+        <[u8; 1]>::try_from([256].into_iter().collect::<Value<'_>>()).unwrap_err();
+        <[bool; 0]>::try_from(Value::from(true)).unwrap_err();
+    }
+
+    #[test]
+    fn total_eq_and_ord() {
+        let nan = Value::from(f64::NAN);
+        assert_ne!(nan, nan);
+        assert!(nan.eq_total(&nan));
+        assert!(!Value::from(0.0).eq_total(&Value::from(-0.0)));
+        assert!(!Value::from(1).eq_total(&Value::from(1.0)));
+
+        let array = |values: Vec<Value<'static>>| values.into_iter().collect::<Value<'_>>();
+        let values = [
+            Value::from(2.0),
+            array(vec![nan.clone(), Value::from(1)]),
+            Value::from("b"),
+            nan.clone(),
+            Value::from(1),
+            array(vec![nan.clone()]),
+            Value::from("a"),
+            Value::from(2.0),
+            array(vec![nan.clone(), Value::from(1)]),
+            nan.clone(),
+        ];
+        let set = values
+            .into_iter()
+            .map(OrderedValue)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|v| v.0)
+            .collect::<Vec<_>>();
+        let expected = [
+            Value::from("a"),
+            Value::from("b"),
+            Value::from(1),
+            Value::from(2.0),
+            nan.clone(),
+            array(vec![nan.clone()]),
+            array(vec![nan, Value::from(1)]),
+        ];
+        assert_eq!(set.len(), expected.len());
+        for (value, expected) in set.iter().zip(&expected) {
+            assert!(value.eq_total(expected), "{value:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn total_hash() {
+        use std::collections::HashSet;
+
+        let table = |v: f64| [("a", Value::from(v))].into_iter().collect::<Value<'_>>();
+        let set = [
+            table(f64::NAN),
+            table(f64::NAN),
+            table(1.0),
+            table(-0.0),
+            table(0.0),
+        ]
+        .into_iter()
+        .map(OrderedValue)
+        .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(&OrderedValue(table(f64::NAN))));
+    }
+
+    #[test]
+    fn total_order_of_tables() {
+        use core::cmp::Ordering;
+        use std::collections::HashSet;
+
+        // Equal tables, whatever the order of insertion or the map backing them.
+        let table = |keys: &mut dyn Iterator<Item = u32>| {
+            keys.map(|i| (i.to_string(), Value::from(i64::from(i))))
+                .collect::<Value<'_>>()
+        };
+        let a = table(&mut (0..100));
+        let b = table(&mut (0..100).rev());
+        assert!(a.eq_total(&b));
+        assert_eq!(a.cmp_total(&b), Ordering::Equal);
+        let set = [a.clone(), b.clone()]
+            .into_iter()
+            .map(OrderedValue)
+            .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 1);
+
+        // Entries are compared in key order.
+        let c = table(&mut (1..100).rev());
+        assert_eq!(a.cmp_total(&c), Ordering::Less);
+        assert_eq!(c.cmp_total(&b), Ordering::Greater);
+    }
+
+    #[test]
+    fn as_accessors_borrow_self() {
+        use crate::parse;
+
+        // Owned values can be accessed through a short-lived borrow.
+        let table = parse(r#"a = { b = ["c"] }"#).unwrap().into_owned();
+        let c = table
+            .get("a")
+            .and_then(Value::as_table)
+            .and_then(|a| a.get("b"))
+            .and_then(Value::as_array)
+            .and_then(|b| b.get(0))
+            .and_then(Value::as_str);
+        assert_eq!(c, Some("c"));
+    }
+
+    #[test]
+    fn from_str() {
+        use crate::{Error, ParseErrorKind, Table};
+
+        let value: Value<'static> = " [1, 2, 3]\n".parse().unwrap();
+        assert_eq!(value, [1, 2, 3].into_iter().collect::<Value<'_>>());
+        let value: Value<'static> = r#"{ a = "b", c = [true] }"#.parse().unwrap();
+        let expected: Value<'_> = [("a", Value::from("b")), ("c", [true].into_iter().collect())]
+            .into_iter()
+            .collect();
+        assert_eq!(value, expected);
+        assert_eq!(
+            "1979-05-27".parse::<Value<'_>>().unwrap().kind(),
+            ValueKind::Datetime
+        );
+        assert_eq!("'lit'".parse::<Value<'_>>().unwrap(), Value::from("lit"));
+        for invalid in ["", "1 2", "a = 1", "nope"] {
+            match invalid.parse::<Value<'_>>() {
+                Err(Error::Parse(e)) => assert_eq!(e.kind(), ParseErrorKind::UnexpectedChar),
+                r => panic!("unexpected result for {invalid:?}: {r:?}"),
+            }
+        }
+
+        let table: Table<'static> = "a = 1\n[b]\nc = 'd'".parse().unwrap();
+        assert_eq!(table.get("a"), Some(&Value::from(1)));
+        "a = ".parse::<Table<'_>>().unwrap_err();
+    }
+
+    #[test]
+    fn partial_eq_primitives() {
+        let value = Value::from("hello");
+        assert_eq!(value, "hello");
+        assert_eq!(value, *"hello");
+        assert_eq!(value, String::from("hello"));
+        assert_ne!(value, "world");
+        assert_ne!(value, 42);
+
+        assert_eq!(Value::from(42), 42);
+        assert_eq!(Value::from(4.2), 4.2);
+        assert_ne!(Value::from(f64::NAN), f64::NAN);
+        assert_eq!(Value::from(true), true);
+        assert_ne!(Value::from(1), true);
+
+        let array = ["a", "b"].into_iter().collect::<Value<'_>>();
+        assert_eq!(array, ["a", "b"]);
+        assert_eq!(array, ["a", "b"][..]);
+        assert_ne!(array, ["a"]);
+        assert_ne!(Value::from("a"), ["a"]);
+        let nested = [[1, 2].into_iter().collect::<Value<'_>>()]
+            .into_iter()
+            .collect::<Value<'_>>();
+        assert_eq!(nested, [[1, 2]]);
+
+        // Through references, as returned by the accessors.
+        let table = crate::parse("features = [\"rt\", \"net\"]\nd = 1979-05-27").unwrap();
+        assert_eq!(table.get("features").unwrap(), &["rt", "net"]);
+        assert_ne!(table.get("features").unwrap(), &["rt"]);
+        let date = "1979-05-27".parse::<crate::Datetime>().unwrap();
+        assert_eq!(table.get("d").unwrap(), &date);
+    }
+
+    #[test]
+    fn get_and_pointer() {
+        let value = Value::from(
+            crate::parse(
+                r#"
+                a = [1, { b = "c" }]
+                "~x/y" = true
+                [d.e]
+                f = 2.5
+                "#,
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(value.get("a").and_then(|a| a.get(0)), Some(&Value::from(1)));
+        assert_eq!(value.get(String::from("~x/y")), Some(&Value::from(true)));
+        assert!(value.get("a").and_then(|a| a.get("b")).is_none());
+        assert!(value.get(0).is_none());
+        assert!(value.get("z").is_none());
+
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/a/1/b"), Some(&Value::from("c")));
+        assert_eq!(value.pointer("/d/e/f"), Some(&Value::from(2.5)));
+        assert_eq!(value.pointer("/~0x~1y"), Some(&Value::from(true)));
+        for pointer in ["a", "/a/2", "/a/01", "/a/+1", "/a/0/b", "/d/e/f/g", "/"] {
+            assert!(value.pointer(pointer).is_none(), "{pointer}");
+        }
+    }
+
+    #[test]
+    fn as_mut() {
+        let mut table = crate::parse("[package]\nkeywords = [\"toml\"]").unwrap();
+        table
+            .get_mut("package")
+            .and_then(Value::as_table_mut)
+            .and_then(|package| package.get_mut("keywords"))
+            .and_then(Value::as_array_mut)
+            .unwrap()
+            .push("parser".into());
+        let keywords = table.get("package").and_then(|p| p.get("keywords"));
+        assert_eq!(keywords.unwrap(), &["toml", "parser"]);
+
+        let mut value = Value::Integer(1);
+        assert!(value.as_table_mut().is_none());
+        assert!(value.as_array_mut().is_none());
+    }
+
+    #[test]
+    fn typed_array_vecs() {
+        let table = crate::parse(
+            r#"
+            strings = ["a", "b"]
+            integers = [1, -2]
+            floats = [1.5, inf]
+            booleans = [true, false]
+            mixed = ["a", 1]
+            empty = []
+            "#,
+        )
+        .unwrap();
+        let array = |key| table.get(key).and_then(Value::as_array).unwrap();
+
+        assert_eq!(array("strings").as_str_vec(), Some(vec!["a", "b"]));
+        assert_eq!(array("integers").as_i64_vec(), Some(vec![1, -2]));
+        assert_eq!(array("floats").as_f64_vec(), Some(vec![1.5, f64::INFINITY]));
+        assert_eq!(array("booleans").as_bool_vec(), Some(vec![true, false]));
+        assert_eq!(array("empty").as_str_vec(), Some(vec![]));
+
+        let mixed = array("mixed");
+        assert!(mixed.as_str_vec().is_none());
+        assert!(mixed.as_i64_vec().is_none());
+        assert!(array("integers").as_f64_vec().is_none());
+        assert!(array("strings").as_bool_vec().is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_generic_containers() {
+        use crate::{Datetime, Table};
+        use alloc::string::String;
+        use std::collections::HashMap;
+
+        let toml = r#"
+            int = 1
+            float = 1.0
+            string = "a"
+            offset-datetime = 1979-05-27T07:32:00Z
+            local-date = 1979-05-27
+            local-time = 07:32:00
+            mixed = [1, 2.5, 1979-05-27]
+            table = { date = 1, time = { hour = 7 } }
+        "#;
+        let map: HashMap<String, Value<'_>> = crate::from_str(toml).unwrap();
+        assert_eq!(map["int"], Value::Integer(1));
+        assert_eq!(map["float"], Value::Float(1.0));
+        assert_eq!(map["string"], Value::from("a"));
+        let datetime = |s: &str| Value::Datetime(s.parse::<Datetime>().unwrap());
+        assert_eq!(map["offset-datetime"], datetime("1979-05-27T07:32:00Z"));
+        assert_eq!(map["local-date"], datetime("1979-05-27"));
+        assert_eq!(map["local-time"], datetime("07:32:00"));
+        assert_eq!(
+            map["mixed"],
+            [Value::Integer(1), Value::Float(2.5), datetime("1979-05-27")]
+                .into_iter()
+                .collect::<Value<'_>>()
+        );
+        // Tables with datetime-like keys are still tables.
+        let table = map["table"].as_table().unwrap();
+        assert_eq!(table.get("date"), Some(&Value::Integer(1)));
+        assert!(table.get("time").unwrap().as_table().is_some());
+
+        let table: Table<'_> = crate::from_str(toml).unwrap();
+        assert_eq!(table.len(), map.len());
+        for (key, value) in &map {
+            assert_eq!(table.get(key), Some(value), "{key}");
+        }
+
+        // Other formats.
+        let value: Value<'_> = serde_json::from_str(r#"{"a": 1, "b": 1.0, "c": ["d"]}"#).unwrap();
+        let table = value.as_table().unwrap();
+        assert_eq!(table.get("a"), Some(&Value::Integer(1)));
+        assert_eq!(table.get("b"), Some(&Value::Float(1.0)));
+        assert_eq!(table.get("c").unwrap(), &["d"]);
+        assert!(serde_json::from_str::<Value<'_>>("18446744073709551615").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_integers_and_floats() {
+        #[derive(serde::Deserialize)]
+        struct Config<'a> {
+            #[serde(borrow)]
+            x: Value<'a>,
+        }
+
+        let x = |toml| crate::from_str::<Config<'_>>(toml).unwrap().x;
+        assert_eq!(x("x = 1"), Value::Integer(1));
+        assert_eq!(x("x = 0x10"), Value::Integer(16));
+        assert_eq!(x("x = 1.0"), Value::Float(1.0));
+        assert_eq!(x("x = -0.0"), Value::Float(-0.0));
+        assert_eq!(x("x = 1e3"), Value::Float(1000.0));
+        assert_eq!(x("x = inf"), Value::Float(f64::INFINITY));
+        assert!(x("x = nan").as_f64().unwrap().is_nan());
+        assert_eq!(
+            x("x = [1, 1.0]"),
+            [Value::Integer(1), Value::Float(1.0)]
+                .into_iter()
+                .collect::<Value<'_>>()
+        );
+    }
+
+    #[test]
+    fn display_value() {
+        use alloc::string::ToString;
+
+        for (value, expected) in [
+            (Value::from("a \"b\"\n\u{1}"), r#""a \"b\"\n\u0001""#),
+            (Value::from(-7), "-7"),
+            (Value::from(1.0), "1.0"),
+            (Value::from(-0.5), "-0.5"),
+            (Value::from(1e300), "1e300"),
+            (Value::from(f64::INFINITY), "inf"),
+            (Value::from(f64::NEG_INFINITY), "-inf"),
+            (Value::from(f64::NAN), "nan"),
+            (Value::from(true), "true"),
+            ([1, 2].into_iter().collect(), "[1, 2]"),
+            (Value::from(crate::Table::new()), "{}"),
+        ] {
+            assert_eq!(value.to_string(), expected);
+        }
+
+        // Every value should parse back to itself.
+        let table = crate::parse(
+            r#"v = { a = "x", "b c" = [1, 2.5, { d = 1979-05-27T07:32:00Z }], e = {} }"#,
+        )
+        .unwrap();
+        let value = table.get("v").unwrap();
+        let text = value.to_string();
+        #[cfg(not(feature = "hashbrown"))]
+        assert_eq!(
+            text,
+            r#"{ a = "x", "b c" = [1, 2.5, { d = 1979-05-27T07:32:00Z }], e = {} }"#
+        );
+        let input = alloc::format!("v = {text}");
+        let reparsed = crate::parse(&input).unwrap();
+        assert_eq!(reparsed.get("v"), Some(value));
+    }
+
+    #[test]
+    fn float_round_trip() {
+        use alloc::{format, string::ToString};
+
+        let edge_cases = [
+            0.0,
+            -0.0,
+            1.0,
+            0.1,
+            1e-7,
+            1e16,
+            f64::MIN_POSITIVE,
+            f64::MAX,
+            f64::MIN,
+            f64::EPSILON,
+            5e-324,
+        ];
+        // A simple xorshift generator, so the test is deterministic.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let random = core::iter::repeat_with(|| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            f64::from_bits(state)
+        })
+        .filter(|f| f.is_finite())
+        .take(10_000);
+
+        for f in edge_cases.into_iter().chain(random) {
+            let text = Value::from(f).to_string();
+            let input = format!("f = {text}");
+            let table = crate::parse(&input).unwrap_or_else(|e| panic!("{text}: {e}"));
+            let parsed = table.get("f").and_then(Value::as_f64);
+            assert_eq!(parsed.map(f64::to_bits), Some(f.to_bits()), "{text}");
+        }
+    }
+
+    #[test]
+    fn special_floats() {
+        use alloc::string::ToString;
+
+        for (input, expected, text) in [
+            ("inf", f64::INFINITY, "inf"),
+            ("+inf", f64::INFINITY, "inf"),
+            ("-inf", f64::NEG_INFINITY, "-inf"),
+            ("nan", f64::NAN, "nan"),
+            ("+nan", f64::NAN, "nan"),
+            ("-nan", f64::NAN, "nan"),
+        ] {
+            let input = alloc::format!("a = {input}");
+            let table = crate::parse(&input).unwrap();
+            let value = table.get("a").unwrap();
+            let f = value.as_f64().unwrap();
+            assert_eq!(f.to_bits(), expected.to_bits(), "{input}");
+            assert_eq!(value.to_string(), text, "{input}");
+
+            let input = alloc::format!("a = {value}");
+            let reparsed = crate::parse(&input).unwrap();
+            assert!(reparsed.get("a").unwrap().eq_total(value), "{input}");
+        }
+        assert_eq!(
+            crate::parse("a = -inf").unwrap().get("a"),
+            Some(&Value::Float(f64::NEG_INFINITY))
+        );
+    }
+}
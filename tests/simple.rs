@@ -120,15 +120,25 @@ fn simple_cargo_toml_serde() {
     let serde = manifest.dependencies().unwrap().by_name("serde").unwrap();
     assert_eq!(serde.version().unwrap(), "1.0");
     assert_eq!(
-        serde
-            .features()
-            .map(|f| f.map(|s| s).collect::<Vec<_>>())
-            .as_deref(),
+        serde.features().map(|f| f.collect::<Vec<_>>()).as_deref(),
         Some(&["std", "derive"][..])
     );
 
     let regex = manifest.dependencies().unwrap().by_name("regex").unwrap();
     assert_eq!(regex.version().unwrap(), "1.5");
+    // The simple and detailed forms result in the same `Dependency`.
+    let detailed: Manifest =
+        tomling::from_str("[dependencies]\nregex = { version = \"1.5\" }").unwrap();
+    assert_eq!(
+        detailed.dependencies().unwrap().by_name("regex").unwrap(),
+        regex
+    );
+    let dep_from_path = manifest
+        .dependencies()
+        .unwrap()
+        .by_name("dep-from-path")
+        .unwrap();
+    assert!(dep_from_path.version().is_none());
     let dep_from_git = manifest
         .dependencies()
         .unwrap()
@@ -160,6 +170,868 @@ fn simple_cargo_toml_serde() {
     assert_eq!(binary.path(), Some("src/bin/my-binary.rs"));
 }
 
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_badges() {
+    use tomling::{cargo::Manifest, Value};
+
+    let toml = r#"
+        [package]
+        name = "example"
+
+        [badges]
+        maintenance = { status = "actively-developed" }
+    "#;
+    let manifest: Manifest = tomling::from_str(toml).unwrap();
+    let maintenance = manifest
+        .badges()
+        .unwrap()
+        .get("maintenance")
+        .and_then(Value::as_table)
+        .unwrap();
+    assert_eq!(
+        maintenance.get("status").and_then(Value::as_str),
+        Some("actively-developed")
+    );
+
+    let manifest: Manifest = tomling::from_str(CARGO_TOML).unwrap();
+    assert!(manifest.badges().is_none());
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_feature_closure() {
+    use tomling::cargo::{FeatureRef, Manifest};
+
+    let toml = r#"
+        [features]
+        a = ["b", "dep:serde", "regex/std"]
+        b = ["c", "serde?/derive"]
+        c = ["a", "regex"]
+    "#;
+    let manifest: Manifest = tomling::from_str(toml).unwrap();
+    let features = manifest.features().unwrap();
+    // Cycles are followed only once.
+    assert_eq!(features.closure("a"), ["a", "b", "c", "regex"]);
+    assert_eq!(features.closure("c"), ["a", "b", "c", "regex"]);
+    assert_eq!(
+        features.refs_by_name("b").unwrap().collect::<Vec<_>>(),
+        [
+            FeatureRef::Feature("c"),
+            FeatureRef::DepFeature {
+                dep: "serde",
+                feature: "derive",
+                weak: true,
+            },
+        ],
+    );
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_license_file() {
+    use tomling::cargo::Manifest;
+
+    let toml = r#"
+        [package]
+        name = "foo"
+        license-file = "LICENSE.txt"
+
+        [workspace.package]
+        license-file = "../LICENSE"
+    "#;
+    let manifest: Manifest = tomling::from_str(toml).unwrap();
+    let package = manifest.package().unwrap();
+    assert_eq!(package.license_file(), Some("LICENSE.txt".into()));
+    let workspace = manifest.workspace().unwrap().package().unwrap();
+    assert_eq!(workspace.license_file(), Some("../LICENSE"));
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_resolve_inherited() {
+    use tomling::cargo::{package::WorkspaceInheritable, Manifest, Publish, RustEdition};
+
+    let workspace = r#"
+        [workspace]
+        members = ["member"]
+
+        [workspace.package]
+        version = "1.2.3"
+        edition = "2021"
+        rust-version = "1.75"
+        license-file = "LICENSE"
+        publish = false
+    "#;
+    let member = r#"
+        [package]
+        name = "member"
+        version.workspace = true
+        edition.workspace = true
+        rust-version.workspace = true
+        license-file.workspace = true
+        publish.workspace = true
+        description.workspace = true
+        repository = "https://example.com/member"
+    "#;
+    let workspace: Manifest = tomling::from_str(workspace).unwrap();
+    let workspace = workspace.workspace().unwrap().package().unwrap();
+    let member: Manifest = tomling::from_str(member).unwrap();
+    let package = member.package().unwrap();
+    assert_eq!(package.version(), Some(WorkspaceInheritable::Inherited));
+
+    let resolved = package.resolve_inherited(workspace);
+    assert_eq!(resolved.version(), Some("1.2.3".into()));
+    assert_eq!(resolved.edition(), Some(&RustEdition::E2021.into()));
+    assert_eq!(resolved.rust_version(), Some("1.75".into()));
+    assert_eq!(resolved.license_file(), Some("LICENSE".into()));
+    assert_eq!(resolved.publish(), Some(&Publish::Flag(false).into()));
+    assert_eq!(
+        resolved.repository(),
+        Some("https://example.com/member".into())
+    );
+    // Not set in the workspace.
+    assert_eq!(
+        resolved.description(),
+        Some(WorkspaceInheritable::Inherited)
+    );
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_raw() {
+    use tomling::{
+        cargo::{Manifest, ManifestWithRaw},
+        Value,
+    };
+
+    let toml = r#"
+        [package]
+        name = "foo"
+        version = "0.1.0"
+        some-future-key = "value"
+
+        [some-future-section]
+        enabled = true
+    "#;
+    let manifest: ManifestWithRaw = tomling::from_str(toml).unwrap();
+    assert_eq!(manifest.package().unwrap().name(), "foo");
+    let raw = manifest.raw();
+    assert_eq!(
+        raw.get("some-future-section")
+            .and_then(Value::as_table)
+            .and_then(|t| t.get("enabled")),
+        Some(&Value::Boolean(true))
+    );
+
+    let table = tomling::parse(toml).unwrap();
+    let manifest = ManifestWithRaw::from_table(table.clone()).unwrap();
+    assert_eq!(manifest.raw(), &table);
+    let (manifest, raw) = manifest.into_parts();
+    assert_eq!(manifest.package().unwrap().name(), "foo");
+    let package = raw.get("package").and_then(Value::as_table);
+    assert_eq!(
+        package.and_then(|p| p.get("some-future-key")),
+        Some(&Value::from("value"))
+    );
+
+    // Without keeping the table.
+    let manifest = Manifest::from_table(table).unwrap();
+    assert_eq!(manifest.package().unwrap().name(), "foo");
+    assert!(Manifest::from_table(tomling::parse("package = 1").unwrap()).is_err());
+    assert!(ManifestWithRaw::from_table(tomling::parse("package = 1").unwrap()).is_err());
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_manifest_kind() {
+    use tomling::cargo::{Manifest, ManifestKind};
+
+    let kind = |toml: &str| {
+        let manifest: Manifest = tomling::from_str(toml).unwrap();
+        (
+            manifest.package_or_workspace(),
+            manifest.is_virtual_workspace(),
+        )
+    };
+    let package = "[package]\nname = \"foo\"\nversion = \"0.1.0\"\n";
+    let workspace = "[workspace]\nmembers = [\"foo\"]\n";
+
+    assert_eq!(kind(package), (Some(ManifestKind::Package), false));
+    assert_eq!(kind(workspace), (Some(ManifestKind::Workspace), true));
+    assert_eq!(
+        kind(&format!("{package}{workspace}")),
+        (Some(ManifestKind::Both), false)
+    );
+    assert_eq!(kind(""), (None, false));
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_profiles() {
+    use tomling::{
+        cargo::{Manifest, OptLevel},
+        Value,
+    };
+
+    let toml = r#"
+        [profile.dev]
+        opt-level = 1
+        debug = "line-tables-only"
+
+        [profile.release]
+        opt-level = "s"
+        lto = true
+        codegen-units = 1
+
+        [profile.release.build-override]
+        opt-level = 0
+
+        [profile.release.package."*"]
+        opt-level = 2
+
+        [profile.release.package.foo]
+        debug-assertions = true
+
+        [profile.dist]
+        inherits = "release"
+        lto = "fat"
+        strip = true
+
+        [profile.dist.package.foo]
+        overflow-checks = true
+
+        [profile.a]
+        inherits = "b"
+        panic = "abort"
+
+        [profile.b]
+        inherits = "a"
+        incremental = false
+    "#;
+    let manifest: Manifest = tomling::from_str(toml).unwrap();
+    let profiles = manifest.profiles().unwrap();
+
+    let release = profiles.by_name("release").unwrap();
+    assert_eq!(release.opt_level(), Some(OptLevel::Size));
+    assert_eq!(
+        release.build_override().unwrap().opt_level(),
+        Some(OptLevel::Level(0))
+    );
+    assert_eq!(
+        release.package("*").unwrap().opt_level(),
+        Some(OptLevel::Level(2))
+    );
+    assert_eq!(release.packages().unwrap().count(), 2);
+
+    let dist = profiles.resolve("dist").unwrap();
+    assert_eq!(dist.inherits(), Some("release"));
+    assert_eq!(dist.opt_level(), Some(OptLevel::Size));
+    assert_eq!(dist.lto(), Some(&Value::from("fat")));
+    assert_eq!(dist.strip(), Some(&Value::Boolean(true)));
+    assert_eq!(dist.codegen_units(), Some(1));
+    assert!(dist.build_override().is_some());
+    let foo = dist.package("foo").unwrap();
+    assert_eq!(foo.debug_assertions(), Some(true));
+    assert_eq!(foo.overflow_checks(), Some(true));
+
+    // `bench` inherits from `release` and `test` from `dev`, even if not in the manifest.
+    assert_eq!(profiles.resolve("bench").as_ref(), Some(release));
+    let test = profiles.resolve("test").unwrap();
+    assert_eq!(test.opt_level(), Some(OptLevel::Level(1)));
+    assert_eq!(test.debug(), Some(&Value::from("line-tables-only")));
+    assert!(profiles.resolve("unknown").is_none());
+
+    // Cycles are broken.
+    let a = profiles.resolve("a").unwrap();
+    assert_eq!(a.panic(), Some("abort"));
+    assert_eq!(a.incremental(), Some(false));
+
+    assert!(tomling::from_str::<Manifest>("[profile.dev]\nopt-level = 4").is_err());
+}
+
+#[cfg(all(feature = "cargo-toml", feature = "std"))]
+#[test]
+fn cargo_toml_from_path() {
+    use tomling::{cargo::Manifest, Error};
+
+    // Our own manifest.
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+    let manifest = Manifest::from_path(path).unwrap();
+    let package = manifest.package().unwrap();
+    assert_eq!(package.name(), "tomling");
+    let features = manifest.features().unwrap();
+    assert_eq!(features.by_name("cargo-toml").unwrap(), &["serde"]);
+
+    // A manifest using more features, with nothing borrowed from the file contents.
+    let path = std::env::temp_dir().join("tomling-cargo-toml-from-path.toml");
+    std::fs::write(&path, CARGO_TOML).unwrap();
+    let manifest = Manifest::from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let package = manifest.package().unwrap();
+    let authors = package.authors().unwrap();
+    let alice = authors.uninherited().unwrap().next().unwrap();
+    assert_eq!(alice.name(), "Alice Great");
+    assert_eq!(alice.email(), Some("foo@bar.com"));
+    let serde = manifest.dependencies().unwrap().by_name("serde").unwrap();
+    assert_eq!(serde.version(), Some("1.0"));
+    let default = manifest.features().unwrap().by_name("default").unwrap();
+    assert_eq!(default, &["serde"]);
+
+    let e = Manifest::from_path("does/not/exist/Cargo.toml").unwrap_err();
+    let Error::Io(io) = &e else {
+        panic!("expected an I/O error, got {e:?}");
+    };
+    assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(io.path(), std::path::Path::new("does/not/exist/Cargo.toml"));
+    let msg = e.to_string();
+    assert!(
+        msg.starts_with("failed to read does/not/exist/Cargo.toml: "),
+        "{msg}"
+    );
+    assert!(
+        msg.len() > "failed to read does/not/exist/Cargo.toml: ".len(),
+        "{msg}"
+    );
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_workspace_dependencies() {
+    use tomling::cargo::{dependency::Source, Manifest};
+
+    let manifest: Manifest = tomling::from_str(
+        r#"
+        [workspace]
+        members = ["a"]
+
+        [workspace.dependencies]
+        serde = { version = "1", features = ["derive"], default-features = false }
+        regex = "1.5"
+        zvariant = { version = "5.0.0", path = "zvariant", optional = true }
+        dep-from-git = { git = "https://github.com/zeenix/dep-from-git", tag = "v1" }
+        "#,
+    )
+    .unwrap();
+    let deps = manifest.workspace().unwrap().dependencies().unwrap();
+    assert_eq!(deps.len(), 4);
+
+    let serde = deps.by_name("serde").unwrap();
+    assert_eq!(serde.version(), Some("1"));
+    assert_eq!(serde.features().unwrap().collect::<Vec<_>>(), &["derive"]);
+    assert_eq!(serde.default_features(), Some(false));
+    assert_eq!(serde.source(), None);
+
+    let regex = deps.by_name("regex").unwrap();
+    assert_eq!(regex.version(), Some("1.5"));
+    assert_eq!(regex.default_features(), None);
+
+    let zvariant = deps.by_name("zvariant").unwrap();
+    assert_eq!(zvariant.version(), Some("5.0.0"));
+    assert_eq!(zvariant.optional(), Some(true));
+    assert_eq!(zvariant.source().and_then(Source::path), Some("zvariant"));
+
+    let git = deps.by_name("dep-from-git").unwrap();
+    assert_eq!(git.version(), None);
+    let git = git.source().and_then(Source::git).unwrap();
+    assert_eq!(git.repository(), "https://github.com/zeenix/dep-from-git");
+    assert_eq!(git.commit().and_then(|c| c.tag()), Some("v1"));
+
+    // With nothing borrowed from the input.
+    let path = std::env::temp_dir().join("tomling-cargo-toml-workspace-dependencies.toml");
+    std::fs::write(
+        &path,
+        "[workspace.dependencies]\n\
+         serde = { version = \"1.0.200\", features = [\"derive\"], default-features = false }",
+    )
+    .unwrap();
+    let manifest = Manifest::from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let deps = manifest.workspace().unwrap().dependencies().unwrap();
+    let serde = deps.by_name("serde").unwrap();
+    assert_eq!(serde.version(), Some("1.0.200"));
+    assert_eq!(serde.features().unwrap().collect::<Vec<_>>(), &["derive"]);
+    assert_eq!(serde.default_features(), Some(false));
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_workspace_lints() {
+    use tomling::cargo::{LintLevel, Manifest};
+
+    let manifest: Manifest = tomling::from_str(
+        r#"
+        [workspace.lints.rust]
+        unsafe_code = "forbid"
+        unexpected_cfgs = { level = "warn", check-cfg = ['cfg(tokio_unstable)'] }
+
+        [workspace.lints.clippy]
+        all = { level = "deny", priority = -1 }
+        "#,
+    )
+    .unwrap();
+    let lints = manifest.workspace().unwrap().lints().unwrap();
+    assert!(lints.rustdoc().is_none());
+    assert_eq!(
+        lints.iter().map(|(tool, _)| tool).collect::<Vec<_>>(),
+        &["clippy", "rust"]
+    );
+
+    let rust = lints.rust().unwrap();
+    assert_eq!(rust.len(), 2);
+    let unsafe_code = rust.by_name("unsafe_code").unwrap();
+    assert_eq!(unsafe_code.level(), LintLevel::Forbid);
+    assert!(unsafe_code.options().is_empty());
+    let unexpected_cfgs = rust.by_name("unexpected_cfgs").unwrap();
+    assert_eq!(unexpected_cfgs.level(), LintLevel::Warn);
+    assert_eq!(
+        unexpected_cfgs.option("check-cfg").unwrap(),
+        &["cfg(tokio_unstable)"]
+    );
+    // The level isn't an option.
+    assert_eq!(unexpected_cfgs.options().len(), 1);
+
+    let all = lints.clippy().unwrap().by_name("all").unwrap();
+    assert_eq!(all.level(), LintLevel::Deny);
+    assert_eq!(all.priority(), Some(-1));
+
+    // Package lints, either inherited or not.
+    let manifest: Manifest = tomling::from_str("[lints]\nworkspace = true").unwrap();
+    assert!(manifest.lints().unwrap().inherited());
+    let manifest: Manifest = tomling::from_str("[lints.rust]\nunsafe_code = \"deny\"").unwrap();
+    let lints = manifest.lints().and_then(|l| l.uninherited_ref()).unwrap();
+    let unsafe_code = lints.rust().and_then(|r| r.by_name("unsafe_code")).unwrap();
+    assert_eq!(unsafe_code.level(), LintLevel::Deny);
+
+    for invalid in [
+        "[lints]\nworkspace = false",
+        "[workspace.lints.rust]\nunsafe_code = \"never\"",
+        "[workspace.lints.rust]\nunsafe_code = { priority = 1 }",
+        "[workspace.lints.rust]\nunsafe_code = { level = \"deny\", priority = \"high\" }",
+    ] {
+        assert!(
+            tomling::from_str::<Manifest>(invalid).is_err(),
+            "{invalid:?}"
+        );
+    }
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_rust_version() {
+    use tomling::cargo::RustVersion;
+
+    for (version, expected) in [
+        ("1", RustVersion::new(1, 0, 0)),
+        ("1.80", RustVersion::new(1, 80, 0)),
+        ("1.80.0", RustVersion::new(1, 80, 0)),
+        ("1.70.2", RustVersion::new(1, 70, 2)),
+    ] {
+        assert_eq!(
+            version.parse::<RustVersion>().unwrap(),
+            expected,
+            "{version}"
+        );
+    }
+    for invalid in ["", "1.", ".1", "1.80.0.1", "1.+80", "1.80-beta", "v1.80"] {
+        assert!(invalid.parse::<RustVersion>().is_err(), "{invalid}");
+    }
+    assert!(RustVersion::new(1, 9, 0) < RustVersion::new(1, 80, 0));
+    assert_eq!(RustVersion::new(1, 80, 1).to_string(), "1.80.1");
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_error_path() {
+    use tomling::cargo::{Binary, Dependencies, Manifest, Package};
+
+    #[derive(Debug, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Partial<'a> {
+        #[serde(borrow)]
+        package: Option<Package<'a>>,
+        dependencies: Option<Dependencies<'a>>,
+        bin: Option<Vec<Binary<'a>>>,
+    }
+
+    for (input, path) in [
+        (
+            "[package]\nname = \"a\"\nedition = \"2019\"",
+            "package.edition",
+        ),
+        ("[dependencies]\nfoo = { version = 1 }", "dependencies.foo"),
+        ("[[bin]]\nname = \"a\"\n[[bin]]\nname = 1", "bin[1].name"),
+        (
+            "[dependencies]\n\"foo.bar\" = 1",
+            "dependencies.\"foo.bar\"",
+        ),
+    ] {
+        let e = tomling::from_str::<Partial<'_>>(input).unwrap_err();
+        assert!(e.is_deserialize(), "{input:?}");
+        assert_eq!(e.path(), Some(path), "{input:?}");
+        assert!(
+            e.to_string().ends_with(&format!(" at {path}")),
+            "{input:?}: {e}"
+        );
+
+        // `Manifest` keeps the path in the message.
+        let e = tomling::from_str::<Manifest>(input).unwrap_err();
+        assert!(
+            e.to_string().ends_with(&format!(" at {path}")),
+            "{input:?}: {e}"
+        );
+    }
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_publish() {
+    use tomling::cargo::{Manifest, Publish};
+
+    for (publish, expected, publishable) in [
+        ("false", Publish::Flag(false), false),
+        ("true", Publish::Flag(true), true),
+        (
+            "[\"r1\", \"r2\"]",
+            Publish::Registries(vec!["r1".into(), "r2".into()]),
+            true,
+        ),
+        ("[]", Publish::Registries(vec![]), false),
+    ] {
+        assert_eq!(expected.is_publishable(), publishable, "{publish}");
+        let input = format!("[package]\nname = \"a\"\npublish = {publish}");
+        let manifest: Manifest = tomling::from_str(&input).unwrap();
+        let package = manifest.package().unwrap();
+        assert_eq!(
+            package.publish(),
+            Some(&expected.clone().into()),
+            "{publish}"
+        );
+
+        let input = format!("[workspace.package]\npublish = {publish}");
+        let manifest: Manifest = tomling::from_str(&input).unwrap();
+        let package = manifest.workspace().unwrap().package().unwrap();
+        assert_eq!(package.publish(), Some(&expected), "{publish}");
+    }
+
+    for invalid in ["\"r1\"", "[1]"] {
+        let input = format!("[package]\nname = \"a\"\npublish = {invalid}");
+        assert!(tomling::from_str::<Manifest>(&input).is_err(), "{invalid}");
+    }
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_all_target_kinds() {
+    use tomling::cargo::{LibraryType, Manifest, TargetConfig};
+
+    let toml = r#"
+        [package]
+        name = "example"
+        version = "0.1.0"
+
+        [lib]
+        path = "src/lib.rs"
+        edition = "2018"
+
+        [[bin]]
+        name = "cli"
+        required-features = ["cli"]
+
+        [[example]]
+        name = "demo"
+        crate-type = ["cdylib"]
+        required-features = ["demo"]
+
+        [[test]]
+        name = "integration"
+        harness = false
+
+        [[bench]]
+        name = "parse"
+        path = "benches/parse.rs"
+        required-features = ["bench"]
+    "#;
+    let manifest: Manifest = tomling::from_str(toml).unwrap();
+
+    let library = manifest.library().unwrap();
+    let mut targets: Vec<&dyn TargetConfig> = vec![library];
+    targets.extend(
+        manifest
+            .binaries()
+            .unwrap()
+            .iter()
+            .map(|t| t as &dyn TargetConfig),
+    );
+    targets.extend(
+        manifest
+            .examples()
+            .unwrap()
+            .iter()
+            .map(|t| t as &dyn TargetConfig),
+    );
+    targets.extend(
+        manifest
+            .tests()
+            .unwrap()
+            .iter()
+            .map(|t| t as &dyn TargetConfig),
+    );
+    targets.extend(
+        manifest
+            .benches()
+            .unwrap()
+            .iter()
+            .map(|t| t as &dyn TargetConfig),
+    );
+
+    let required_features: Vec<Vec<&str>> = targets
+        .iter()
+        .map(|t| t.required_features().into_iter().flatten().collect())
+        .collect();
+    assert_eq!(
+        required_features,
+        [vec![], vec!["cli"], vec!["demo"], vec![], vec!["bench"]]
+    );
+    assert_eq!(targets[0].edition(), Some("2018"));
+    assert_eq!(targets[3].harness(), Some(false));
+    assert_eq!(targets[4].path(), Some("benches/parse.rs"));
+
+    let all: Vec<_> = manifest
+        .all_targets()
+        .map(|t| (t.kind(), t.name(), t.path()))
+        .collect();
+    assert_eq!(
+        all,
+        [
+            ("lib", None, Some("src/lib.rs")),
+            ("bin", Some("cli"), None),
+            ("example", Some("demo"), None),
+            ("test", Some("integration"), None),
+            ("bench", Some("parse"), Some("benches/parse.rs")),
+        ]
+    );
+
+    // Examples have their own type, which knows about `crate-type`.
+    let example = &manifest.examples().unwrap()[0];
+    assert_eq!(example.library_type(), Some(&[LibraryType::Cdylib][..]));
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_authors() {
+    use tomling::{cargo::Author, Value};
+
+    let author = |s: &'static str| Author::try_from(Value::from(s));
+
+    let alice = author("Alice Great <foo@bar.com>").unwrap();
+    assert_eq!(alice.name(), "Alice Great");
+    assert_eq!(alice.email(), Some("foo@bar.com"));
+
+    let bob = author("Bob Less").unwrap();
+    assert_eq!(bob.name(), "Bob Less");
+    assert_eq!(bob.email(), None);
+
+    // Only the last `<...>` is the email address.
+    let foo = author("Foo <bar> Baz <a@b.com>").unwrap();
+    assert_eq!(foo.name(), "Foo <bar> Baz");
+    assert_eq!(foo.email(), Some("a@b.com"));
+
+    let no_space = author("Foo<a@b.com>").unwrap();
+    assert_eq!(no_space.name(), "Foo");
+    assert_eq!(no_space.email(), Some("a@b.com"));
+
+    let no_email = author("Foo <bar> Baz").unwrap();
+    assert_eq!(no_email.name(), "Foo <bar> Baz");
+    assert_eq!(no_email.email(), None);
+
+    let no_name = author("<a@b.com>").unwrap();
+    assert_eq!(no_name.name(), "");
+    assert_eq!(no_name.email(), Some("a@b.com"));
+
+    assert!(author("Name <>").unwrap_err().is_convert());
+    assert!(author("Name < >").unwrap_err().is_convert());
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_docs_rs_metadata() {
+    use tomling::cargo::Manifest;
+
+    let toml = r#"
+        [package]
+        name = "example"
+        version = "0.1.0"
+
+        [package.metadata.docs.rs]
+        features = ["serde", "tokio"]
+        default-target = "x86_64-unknown-linux-gnu"
+        targets = ["x86_64-unknown-linux-gnu", "x86_64-pc-windows-msvc"]
+        rustdoc-args = ["--cfg", "docsrs"]
+    "#;
+    let manifest: Manifest = tomling::from_str(toml).unwrap();
+    let docs_rs = manifest.package().unwrap().docs_rs_metadata().unwrap();
+    assert_eq!(docs_rs.all_features(), None);
+    assert_eq!(
+        docs_rs.features().unwrap().collect::<Vec<_>>(),
+        ["serde", "tokio"]
+    );
+    assert_eq!(docs_rs.default_target(), Some("x86_64-unknown-linux-gnu"));
+    assert_eq!(
+        docs_rs.targets().unwrap().collect::<Vec<_>>(),
+        ["x86_64-unknown-linux-gnu", "x86_64-pc-windows-msvc"]
+    );
+    assert!(docs_rs.rustc_args().is_none());
+    assert_eq!(
+        docs_rs.rustdoc_args().unwrap().collect::<Vec<_>>(),
+        ["--cfg", "docsrs"]
+    );
+
+    let toml = r#"
+        [package]
+        name = "example"
+        version = "0.1.0"
+
+        [package.metadata.playground]
+        all-features = true
+    "#;
+    let manifest: Manifest = tomling::from_str(toml).unwrap();
+    assert!(manifest.package().unwrap().docs_rs_metadata().is_none());
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_validate_required_features() {
+    use tomling::cargo::Manifest;
+
+    let toml = r#"
+        [package]
+        name = "example"
+        version = "0.1.0"
+
+        [features]
+        cli = ["dep:clap"]
+        full = ["cli", "serde/derive"]
+
+        [dependencies]
+        clap = { version = "4", optional = true }
+        serde = { version = "1", optional = true }
+
+        [target.'cfg(unix)'.dependencies]
+        nix = { version = "0.29", optional = true }
+
+        [[bin]]
+        name = "cli"
+        required-features = ["cli", "serde", "nix"]
+
+        [[example]]
+        name = "demo"
+        required-features = ["full", "serde/derive"]
+
+        [[test]]
+        name = "integration"
+        required-features = ["clap", "ful"]
+
+        [[bench]]
+        name = "parse"
+        required-features = ["benchmarks"]
+    "#;
+    let manifest: Manifest = tomling::from_str(toml).unwrap();
+    let unknown = manifest.validate_required_features().unwrap_err();
+    let unknown: Vec<_> = unknown
+        .iter()
+        .map(|u| (u.target_kind(), u.target(), u.feature()))
+        .collect();
+    // `clap` is referred to with `dep:`, so it has no implicit feature.
+    assert_eq!(
+        unknown,
+        [
+            ("test", "integration", "clap"),
+            ("test", "integration", "ful"),
+            ("bench", "parse", "benchmarks"),
+        ]
+    );
+    assert_eq!(
+        manifest.validate_required_features().unwrap_err()[1].to_string(),
+        "test `integration` requires the unknown feature `ful`"
+    );
+
+    let manifest: Manifest = tomling::from_str(CARGO_TOML).unwrap();
+    manifest.validate_required_features().unwrap();
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_package_only() {
+    use tomling::cargo::{package_only, Manifest};
+
+    // The dependencies are invalid, but not looked at.
+    let toml = r#"
+        [package]
+        name = "example"
+        version = "0.1.0"
+        rust-version = "1.70"
+
+        [dependencies]
+        serde = 1
+    "#;
+    assert!(tomling::from_str::<Manifest>(toml).is_err());
+    let package = package_only(toml).unwrap();
+    assert_eq!(package.name(), "example");
+    assert_eq!(package.version().unwrap().uninherited(), Some("0.1.0"));
+
+    let package = package_only("package.name = 'dotted'").unwrap();
+    assert_eq!(package.name(), "dotted");
+
+    let err = package_only("[package]\nname = 1").unwrap_err();
+    assert_eq!(err.path(), Some("package.name"));
+    let err = package_only("package = 1").unwrap_err();
+    assert!(err.is_convert());
+    assert_eq!(err.path(), Some("package"));
+    assert!(package_only("[workspace]").unwrap_err().is_deserialize());
+    assert!(package_only("[package").unwrap_err().is_parse());
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn cargo_toml_integer_edition_and_resolver() {
+    use tomling::{
+        cargo::{Manifest, ResolverVersion, RustEdition},
+        Value,
+    };
+
+    for (edition, resolver) in [("\"2021\"", "\"2\""), ("2021", "2")] {
+        let toml = format!(
+            "[package]\nname = \"a\"\nedition = {edition}\nresolver = {resolver}\n\n\
+             [workspace]\nresolver = {resolver}\n"
+        );
+        let manifest: Manifest = tomling::from_str(&toml).unwrap();
+        let package = manifest.package().unwrap();
+        assert_eq!(
+            package.edition().unwrap().uninherited_ref(),
+            Some(&RustEdition::E2021)
+        );
+        assert_eq!(package.resolver(), Some(ResolverVersion::V2));
+        assert_eq!(
+            manifest.workspace().unwrap().resolver(),
+            Some(ResolverVersion::V2)
+        );
+    }
+
+    assert_eq!(
+        RustEdition::try_from(Value::Integer(2015)).unwrap(),
+        RustEdition::E2015
+    );
+    assert!(RustEdition::try_from(Value::Integer(2019)).is_err());
+    assert!(RustEdition::try_from(Value::Float(2021.0)).is_err());
+    assert_eq!(
+        ResolverVersion::try_from(Value::Integer(1)).unwrap(),
+        ResolverVersion::V1
+    );
+    assert!(ResolverVersion::try_from(Value::Integer(4)).is_err());
+    assert!(tomling::from_str::<Manifest>("workspace.resolver = \"two\"").is_err());
+}
+
 const CARGO_TOML: &str = r#"
 [package]
 name = "example"
@@ -60,12 +60,34 @@ fn tokio() {
         })
         .unwrap();
     assert_eq!(version, "0.8.0");
+
+    // All the path dependencies, wherever they are.
+    let mut paths = Vec::new();
+    parsed_map.visit(&mut |path, value| {
+        if path.last().map(|k| k == "path").unwrap_or(false) {
+            let dep = path[..path.len() - 1].join(".");
+            paths.push(format!("{dep} = {}", value.as_str().unwrap()));
+        }
+    });
+    // Sorted, since the iteration order depends on the map backing tables.
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            "dependencies.tokio-macros = ../tokio-macros",
+            "dev-dependencies.tokio-stream = ../tokio-stream",
+            "dev-dependencies.tokio-test = ../tokio-test",
+        ]
+    );
 }
 
 #[cfg(feature = "cargo-toml")]
 #[test]
 fn tokio_serde() {
-    use tomling::cargo::{Manifest, RustEdition};
+    use tomling::{
+        cargo::{Manifest, RustEdition, RustVersion},
+        Value,
+    };
 
     let manifest: Manifest = tomling::from_str(CARGO_TOML).unwrap();
     let package = manifest.package().unwrap();
@@ -75,6 +97,13 @@ fn tokio_serde() {
         package.edition().unwrap().uninherited_ref().unwrap(),
         &RustEdition::E2021
     );
+    let msrv: RustVersion = package
+        .rust_version()
+        .and_then(|v| v.uninherited())
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(msrv, RustVersion::new(1, 70, 0));
 
     let bytes = manifest.dependencies().unwrap().by_name("bytes").unwrap();
     assert_eq!(bytes.version().unwrap(), "1.0.0");
@@ -92,10 +121,7 @@ fn tokio_serde() {
     assert_eq!(socket2.version().unwrap(), "0.5.5");
     assert_eq!(socket2.optional(), Some(true));
     assert_eq!(
-        socket2
-            .features()
-            .map(|f| f.map(|s| s).collect::<Vec<_>>())
-            .as_deref(),
+        socket2.features().map(|f| f.collect::<Vec<_>>()).as_deref(),
         Some(&["all"][..])
     );
 
@@ -120,10 +146,244 @@ fn tokio_serde() {
     assert_eq!(
         windows_sys
             .features()
-            .map(|f| f.map(|s| s).collect::<Vec<_>>())
+            .map(|f| f.collect::<Vec<_>>())
             .as_deref(),
         Some(&["Win32_Foundation", "Win32_Security_Authorization"][..])
     );
+
+    let docs_rs = package.docs_rs_metadata().unwrap();
+    assert_eq!(docs_rs.all_features(), Some(true));
+    assert!(docs_rs.features().is_none());
+    assert!(docs_rs.targets().is_none());
+    assert_eq!(
+        docs_rs.rustdoc_args().unwrap().collect::<Vec<_>>(),
+        [
+            "--cfg",
+            "docsrs",
+            "--cfg",
+            "tokio_unstable",
+            "--cfg",
+            "tokio_taskdump"
+        ],
+    );
+    assert_eq!(
+        docs_rs.rustc_args().unwrap().collect::<Vec<_>>(),
+        ["--cfg", "tokio_unstable", "--cfg", "tokio_taskdump"],
+    );
+    // The rest of the metadata is still available.
+    assert!(package.metadata().unwrap().get("playground").is_some());
+    assert_eq!(
+        package.metadata_path("docs.rs.all-features"),
+        Some(&Value::from(true))
+    );
+    assert_eq!(
+        package.metadata_path("playground.features"),
+        Some(&["full", "test-util"].into_iter().collect())
+    );
+    assert!(package.metadata_path("docs.rs.all-features.nope").is_none());
+    assert!(package.metadata_path("docs.rs.nope").is_none());
+    assert!(package.metadata_path("").is_none());
+
+    let dependencies = manifest.dependencies().unwrap();
+    assert_eq!(dependencies.len(), 5);
+    assert!(!dependencies.is_empty());
+    assert!(dependencies.contains("bytes"));
+    assert!(!dependencies.contains("socket2"));
+    let mut optional = Vec::new();
+    for (name, dep) in dependencies {
+        if dep.optional() == Some(true) {
+            optional.push(name);
+        }
+    }
+    assert_eq!(optional, ["bytes", "mio", "parking_lot", "tokio-macros"]);
+    let targets = manifest.targets().unwrap();
+    assert_eq!(targets.len(), targets.into_iter().count());
+    assert!(targets.contains("cfg(windows)"));
+    assert!(!targets.contains("cfg(nope)"));
+
+    let features = manifest.features().unwrap();
+    assert_eq!(
+        features.closure("full"),
+        [
+            "bytes",
+            "fs",
+            "io-std",
+            "io-util",
+            "libc",
+            "macros",
+            "net",
+            "parking_lot",
+            "process",
+            "rt",
+            "rt-multi-thread",
+            "signal",
+            "signal-hook-registry",
+            "socket2",
+            "sync",
+            "time",
+            "tokio-macros",
+        ]
+    );
+    assert_eq!(features.closure("rt-multi-thread"), ["rt"]);
+    assert_eq!(features.len(), 14);
+    assert!(features.contains("full"));
+    assert!(!features.contains("bytes"));
+    for (name, refs) in features {
+        assert_eq!(features.by_name(name), Some(refs));
+    }
+    assert!(features.closure("default").is_empty());
+    assert!(features.closure("nope").is_empty());
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn tokio_targets_for_cfg() {
+    use tomling::cargo::{CfgCtx, CfgExpr, Manifest};
+
+    let manifest: Manifest = tomling::from_str(CARGO_TOML).unwrap();
+    let targets = manifest.targets().unwrap();
+
+    let expr =
+        CfgExpr::parse(r#"cfg(not(all(target_family = "wasm", target_os = "unknown")))"#).unwrap();
+    assert_eq!(
+        expr,
+        CfgExpr::Not(Box::new(CfgExpr::All(vec![
+            CfgExpr::Eq("target_family", "wasm"),
+            CfgExpr::Eq("target_os", "unknown"),
+        ])))
+    );
+    let exprs = targets
+        .iter_cfg()
+        .map(|(expr, _)| expr.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(exprs.len(), targets.iter().count());
+    assert!(exprs.contains(&CfgExpr::Flag("windows")));
+
+    // Evaluate with a custom predicate: only `tokio_unstable` and `loom` are set.
+    let enabled = exprs
+        .iter()
+        .filter(|expr| {
+            expr.eval(|key, value| value.is_none() && ["tokio_unstable", "loom"].contains(&key))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        enabled,
+        [
+            &CfgExpr::Flag("loom"),
+            &CfgExpr::Not(Box::new(CfgExpr::All(vec![
+                CfgExpr::Eq("target_family", "wasm"),
+                CfgExpr::Eq("target_os", "unknown"),
+            ]))),
+            &CfgExpr::Not(Box::new(CfgExpr::Eq("target_family", "wasm"))),
+            &CfgExpr::Flag("tokio_unstable"),
+        ]
+    );
+
+    let linux = CfgCtx::new()
+        .triple("x86_64-unknown-linux-gnu")
+        .flag("unix")
+        .value("target_family", "unix")
+        .value("target_os", "linux");
+    let keys = targets
+        .for_cfg(&linux)
+        .map(|(key, _)| key)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        keys,
+        [
+            r#"cfg(not(all(target_family = "wasm", target_os = "unknown")))"#,
+            r#"cfg(not(target_family = "wasm"))"#,
+            "cfg(unix)",
+        ]
+    );
+
+    let wasi = CfgCtx::new()
+        .value("target_family", "wasm")
+        .value("target_os", "wasi")
+        .flag("tokio_unstable");
+    let keys = targets
+        .for_cfg(&wasi)
+        .map(|(key, _)| key)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        keys,
+        [
+            r#"cfg(not(all(target_family = "wasm", target_os = "unknown")))"#,
+            "cfg(tokio_unstable)",
+        ]
+    );
+
+    for invalid in [
+        "unix",
+        "cfg(unix",
+        "cfg(foo(unix))",
+        "cfg(target_os = linux)",
+        "cfg(all(unix,,windows))",
+        "cfg(unix, windows)",
+    ] {
+        CfgExpr::parse(invalid).unwrap_err();
+    }
+    CfgExpr::parse("cfg(all(unix, windows,))").unwrap();
+    CfgExpr::parse(&format!(
+        "cfg({}unix{})",
+        "not(".repeat(1000),
+        ")".repeat(1000)
+    ))
+    .unwrap_err();
+}
+
+#[cfg(feature = "cargo-toml")]
+#[test]
+fn tokio_target_dependency_spellings() {
+    use tomling::cargo::{Dependency, Manifest};
+
+    // The same dependencies as in tokio's manifest, with inline tables instead of a header per
+    // dependency.
+    let inline = r#"
+        [target.'cfg(windows)'.dependencies]
+        windows-sys = { version = "0.52", optional = true }
+
+        [target.'cfg(windows)'.dev-dependencies]
+        windows-sys = { version = "0.52", features = [
+          "Win32_Foundation",
+          "Win32_Security_Authorization",
+        ] }
+    "#;
+    // Dotted keys, at the root and in a target table.
+    let dotted = r#"
+        target."cfg(windows)".dependencies.windows-sys = { version = "0.52", optional = true }
+        target."cfg(windows)".dev-dependencies.windows-sys.version = "0.52"
+        target."cfg(windows)".dev-dependencies.windows-sys.features = [
+          "Win32_Foundation",
+          "Win32_Security_Authorization",
+        ]
+    "#;
+    let dotted_in_table = r#"
+        [target."cfg(windows)"]
+        dependencies.windows-sys.version = "0.52"
+        dependencies.windows-sys.optional = true
+        dev-dependencies.windows-sys = { version = "0.52", features = [
+          "Win32_Foundation",
+          "Win32_Security_Authorization",
+        ] }
+    "#;
+    let manifest: Manifest = tomling::from_str(CARGO_TOML).unwrap();
+
+    let windows_sys = |manifest: &Manifest<'static>, dev| -> Dependency<'static> {
+        let target = manifest.targets().unwrap().by_name("cfg(windows)").unwrap();
+        let deps = if dev {
+            target.dev_dependencies()
+        } else {
+            target.dependencies()
+        };
+        deps.unwrap().by_name("windows-sys").unwrap().clone()
+    };
+    for spelling in [inline, dotted, dotted_in_table] {
+        let spelled: Manifest = tomling::from_str(spelling).unwrap();
+        for dev in [false, true] {
+            assert_eq!(windows_sys(&spelled, dev), windows_sys(&manifest, dev));
+        }
+    }
 }
 
 const CARGO_TOML: &str = r#"
@@ -109,7 +109,9 @@ fn zbus() {
 #[test]
 fn zbus_serde() {
     use tomling::{
-        cargo::{LibraryType, Manifest, ResolverVersion, RustEdition},
+        cargo::{
+            FeatureRef, LibraryType, LintLevel, Manifest, ResolverVersion, RustEdition, RustVersion,
+        },
         Value,
     };
 
@@ -119,15 +121,19 @@ fn zbus_serde() {
     assert_eq!(package.name(), "zbus");
     assert_eq!(package.version().unwrap(), "5.1.1".into());
     assert!(package.edition().unwrap().inherited());
+    let docs_rs = package.docs_rs_metadata().unwrap();
+    assert_eq!(docs_rs.all_features(), Some(true));
+    assert_eq!(
+        docs_rs.targets().unwrap().collect::<Vec<_>>(),
+        ["x86_64-unknown-linux-gnu"],
+    );
+    assert!(docs_rs.default_target().is_none());
 
     let serde = manifest.dependencies().unwrap().by_name("serde").unwrap();
     assert!(serde.version().is_none());
     assert_eq!(serde.workspace(), Some(true));
     assert_eq!(
-        serde
-            .features()
-            .map(|f| f.map(|s| s).collect::<Vec<_>>())
-            .as_deref(),
+        serde.features().map(|f| f.collect::<Vec<_>>()).as_deref(),
         Some(&["derive"][..]),
     );
     assert_eq!(serde.package(), Some("serde"));
@@ -136,13 +142,38 @@ fn zbus_serde() {
     assert_eq!(tokio.version().unwrap(), "1.37.0");
     assert!(tokio.optional().unwrap());
     assert_eq!(
-        tokio
-            .features()
-            .map(|f| f.map(|s| s).collect::<Vec<_>>())
-            .as_deref(),
+        tokio.features().map(|f| f.collect::<Vec<_>>()).as_deref(),
         Some(&["rt", "net", "time", "fs", "io-util", "process", "sync", "tracing"][..])
     );
 
+    let features = manifest.features().unwrap();
+    assert_eq!(
+        features
+            .refs_by_name("bus-impl")
+            .unwrap()
+            .collect::<Vec<_>>(),
+        [FeatureRef::Feature("p2p")],
+    );
+    assert_eq!(
+        features.refs_by_name("p2p").unwrap().collect::<Vec<_>>(),
+        [FeatureRef::OptionalDep("rand")],
+    );
+    assert_eq!(
+        features.refs_by_name("uuid").unwrap().collect::<Vec<_>>(),
+        [FeatureRef::DepFeature {
+            dep: "zvariant",
+            feature: "uuid",
+            weak: false,
+        }],
+    );
+    let async_io = features
+        .refs_by_name("async-io")
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(async_io[0], FeatureRef::OptionalDep("async-io"));
+    assert_eq!(async_io[1], FeatureRef::Feature("async-executor"));
+    assert!(features.refs_by_name("nope").is_none());
+
     // The library section.
     let lib = manifest.library().unwrap();
     assert!(!lib.bench().unwrap());
@@ -155,12 +186,16 @@ fn zbus_serde() {
     let bench = manifest.benches().unwrap().first().unwrap();
     assert_eq!(bench.name(), "benchmarks");
     assert!(!bench.harness().unwrap());
+    let kinds: Vec<_> = manifest.all_targets().map(|t| t.kind()).collect();
+    assert_eq!(kinds, ["lib", "example", "example", "bench"]);
+
+    assert!(manifest.lints().unwrap().inherited());
 
     // Now the workspace Cargo.toml.
     let manifest: Manifest = tomling::from_str(WORKSPACE_CARGO_TOML).unwrap();
     let workspace = manifest.workspace().unwrap();
     assert_eq!(
-        workspace.members().unwrap().map(|s| s).collect::<Vec<_>>(),
+        workspace.members().unwrap().collect::<Vec<_>>(),
         &[
             "zbus",
             "zvariant",
@@ -173,24 +208,33 @@ fn zbus_serde() {
         ]
     );
     assert_eq!(workspace.resolver().unwrap(), ResolverVersion::V2);
-    let unexpected_cfgs = match workspace.lints().unwrap().get("rust") {
-        Some(Value::Table(rust)) => match rust.get("unexpected_cfgs") {
-            Some(Value::Table(unexpected_cfgs)) => unexpected_cfgs,
-            _ => panic!(),
-        },
-        _ => panic!(),
-    };
+    let rust_lints = workspace.lints().unwrap().rust().unwrap();
+    let unexpected_cfgs = rust_lints.by_name("unexpected_cfgs").unwrap();
     let package = workspace.package().unwrap();
     assert_eq!(package.edition().unwrap(), RustEdition::E2021);
+    assert!(package.edition().unwrap() >= RustEdition::E2018);
+    let msrv: RustVersion = package.rust_version().unwrap().parse().unwrap();
+    // Compared numerically, not lexicographically.
+    assert!(msrv > "1.8".parse().unwrap());
+    assert!(msrv < "1.80.1".parse().unwrap());
+    let serde = workspace.dependencies().unwrap().by_name("serde").unwrap();
+    assert_eq!(serde.version(), Some("1.0.200"));
+    assert_eq!(serde.default_features(), None);
+    assert_eq!(unexpected_cfgs.level(), LintLevel::Warn);
+    assert_eq!(unexpected_cfgs.priority(), None);
     assert_eq!(
-        unexpected_cfgs.get("level").unwrap().as_str().unwrap(),
-        "warn"
-    );
-    assert_eq!(
-        unexpected_cfgs.get("check-cfg").unwrap(),
+        unexpected_cfgs.option("check-cfg").unwrap(),
         &["cfg(tokio_unstable)"].into_iter().collect::<Value>()
     );
-    // TODO: Check the `profile` section after we add API for that.
+
+    let profiles = manifest.profiles().unwrap();
+    assert_eq!(profiles.iter().count(), 1);
+    let bench = profiles.by_name("bench").unwrap();
+    assert_eq!(bench.debug(), Some(&Value::Boolean(true)));
+    assert_eq!(bench.strip(), Some(&Value::from("none")));
+    assert_eq!(bench.inherits(), None);
+    assert_eq!(bench.opt_level(), None);
+    assert!(profiles.by_name("release").is_none());
 }
 
 const CARGO_TOML: &str = r#"